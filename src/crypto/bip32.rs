@@ -0,0 +1,453 @@
+use utils::base58::{self, Base58Error};
+use crypto::field::PrimeField;
+use crypto::ecdsa::{SECP256K1, PrivateKey, PublicKey, PointFormat, PointError, Scalar};
+use crypto::hash::{hmac_sha512, hash160};
+use std::str::FromStr;
+use std::error::Error;
+use std::fmt;
+
+/// The first hardened child index, the hardened children are derived from the private key only.
+pub const HARDENED: u32 = 1 << 31;
+
+/// Version bytes of the mainnet extended private key (`xprv`).
+pub const XPRV_VERSION: u32 = 0x0488ade4;
+
+/// Version bytes of the mainnet extended public key (`xpub`).
+pub const XPUB_VERSION: u32 = 0x0488b21e;
+
+/// Version bytes of the testnet extended private key (`tprv`).
+pub const TPRV_VERSION: u32 = 0x04358394;
+
+/// Version bytes of the testnet extended public key (`tpub`).
+pub const TPUB_VERSION: u32 = 0x043587cf;
+
+/// Size of the serialized extended key in bytes, without Base58Check checksum.
+pub const ENCODED_SIZE: usize = 78;
+
+/// Key of the master key derivation.
+const MASTER_KEY: &[u8] = b"Bitcoin seed";
+
+/// Extended private key of BIP32 hierarchical deterministic wallet on secp256k1.
+#[derive(Clone)]
+pub struct ExtendedPrivKey {
+	/// Number of the derivations from the master key.
+	depth: u8,
+
+	/// The first 4 bytes of the parent key identifier, zeros for the master key.
+	parent_fingerprint: [u8; 4],
+
+	/// Index of the key in the parent's children, zero for the master key.
+	child_number: u32,
+
+	/// Extra entropy of the child derivation.
+	chain_code: [u8; 32],
+
+	/// The private key.
+	key: PrivateKey
+}
+
+impl ExtendedPrivKey {
+	/// Creates the master key from the seed of 16 to 64 bytes.
+	///
+	/// Returns `InvalidKey` for the negligible part of the seeds, which give the invalid key.
+	pub fn new_master(seed: &[u8]) -> Result<Self, Bip32Error> {
+		if seed.len() < 16 || seed.len() > 64 {
+			return Err(Bip32Error::InvalidSeedLength);
+		}
+
+		let (key, chain_code) = split(&hmac_sha512(MASTER_KEY, seed));
+		let key = Scalar::from_bytes(&key).and_then(PrivateKey::new).ok_or(Bip32Error::InvalidKey)?;
+
+		Ok(ExtendedPrivKey { depth: 0, parent_fingerprint: [0; 4], child_number: 0, chain_code, key })
+	}
+
+	/// Returns the number of the derivations from the master key.
+	pub fn depth(&self) -> u8 { self.depth }
+
+	/// Returns the fingerprint of the parent key.
+	pub fn parent_fingerprint(&self) -> [u8; 4] { self.parent_fingerprint }
+
+	/// Returns the index of the key in the parent's children.
+	pub fn child_number(&self) -> u32 { self.child_number }
+
+	/// Returns the chain code.
+	pub fn chain_code(&self) -> &[u8; 32] { &self.chain_code }
+
+	/// Returns the private key.
+	pub fn private_key(&self) -> &PrivateKey { &self.key }
+
+	/// Returns the key identifier: Hash160 of the compressed public key.
+	pub fn identifier(&self) -> [u8; 20] {
+		hash160(&self.key.public_key().encode(PointFormat::Compressed))
+	}
+
+	/// Returns the fingerprint: the first 4 bytes of the identifier.
+	pub fn fingerprint(&self) -> [u8; 4] {
+		fingerprint(&self.identifier())
+	}
+
+	/// Returns the extended public key with the same chain code and position in the tree.
+	pub fn public_key(&self) -> ExtendedPubKey {
+		ExtendedPubKey {
+			depth: self.depth,
+			parent_fingerprint: self.parent_fingerprint,
+			child_number: self.child_number,
+			chain_code: self.chain_code,
+			key: self.key.public_key()
+		}
+	}
+
+	/// Derives the child key, the index not less than `HARDENED` gives the hardened child.
+	///
+	/// Returns `InvalidKey` for the negligible part of the indices, the next index should be used then.
+	pub fn derive_child(&self, index: u32) -> Result<Self, Bip32Error> {
+		let depth = self.depth.checked_add(1).ok_or(Bip32Error::MaxDepthExceeded)?;
+
+		// The public key gives the fingerprint of the parent, it's computed once
+		let public = self.key.public_key().encode(PointFormat::Compressed);
+
+		// The hardened child uses the private key: 0x00 || k || i, the normal child uses the public key: K || i
+		let mut data = Vec::with_capacity(37);
+		if index >= HARDENED {
+			data.push(0);
+			data.extend_from_slice(&self.key.scalar().to_bytes());
+		} else {
+			data.extend_from_slice(&public);
+		}
+		data.extend_from_slice(&index.to_be_bytes());
+
+		// k_i = I_L + k (mod n), I_L should be less than n and k_i should be non-zero
+		let (tweak, chain_code) = split(&hmac_sha512(&self.chain_code, &data));
+		let tweak = Scalar::from_bytes(&tweak).ok_or(Bip32Error::InvalidKey)?;
+		let key = PrivateKey::new(tweak + *self.key.scalar()).ok_or(Bip32Error::InvalidKey)?;
+
+		let parent_fingerprint = fingerprint(&hash160(&public));
+		Ok(ExtendedPrivKey { depth, parent_fingerprint, child_number: index, chain_code, key })
+	}
+
+	/// Derives the key by the path relative to this key.
+	pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Bip32Error> {
+		path.indices().iter().try_fold(self.clone(), |key, &index| key.derive_child(index))
+	}
+
+	/// Serializes the key in Base58Check with the given version bytes (e.g. `XPRV_VERSION`).
+	pub fn encode(&self, version: u32) -> String {
+		let mut key = [0u8; 33];
+		key[1..].copy_from_slice(&self.key.scalar().to_bytes());
+		encode(version, self.depth, &self.parent_fingerprint, self.child_number, &self.chain_code, &key)
+	}
+
+	/// Deserializes the key from Base58Check, checking the version bytes.
+	pub fn decode(s: &str, version: u32) -> Result<Self, Bip32Error> {
+		let decoded = decode(s, version)?;
+
+		// The private key is prefixed by the zero byte
+		if decoded.key[0] != 0 {
+			return Err(Bip32Error::InvalidEncoding);
+		}
+
+		let mut bytes = [0u8; 32];
+		bytes.copy_from_slice(&decoded.key[1..]);
+		let key = Scalar::from_bytes(&bytes).and_then(PrivateKey::new).ok_or(Bip32Error::InvalidKey)?;
+
+		Ok(ExtendedPrivKey {
+			depth: decoded.depth,
+			parent_fingerprint: decoded.parent_fingerprint,
+			child_number: decoded.child_number,
+			chain_code: decoded.chain_code,
+			key
+		})
+	}
+}
+
+impl fmt::Debug for ExtendedPrivKey {
+	/// Doesn't output the private key and the chain code.
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		write!(f, "ExtendedPrivKey(..)")
+	}
+}
+
+/// Extended public key of BIP32 hierarchical deterministic wallet on secp256k1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtendedPubKey {
+	/// Number of the derivations from the master key.
+	depth: u8,
+
+	/// The first 4 bytes of the parent key identifier, zeros for the master key.
+	parent_fingerprint: [u8; 4],
+
+	/// Index of the key in the parent's children, zero for the master key.
+	child_number: u32,
+
+	/// Extra entropy of the child derivation.
+	chain_code: [u8; 32],
+
+	/// The public key.
+	key: PublicKey
+}
+
+impl ExtendedPubKey {
+	/// Returns the number of the derivations from the master key.
+	pub fn depth(&self) -> u8 { self.depth }
+
+	/// Returns the fingerprint of the parent key.
+	pub fn parent_fingerprint(&self) -> [u8; 4] { self.parent_fingerprint }
+
+	/// Returns the index of the key in the parent's children.
+	pub fn child_number(&self) -> u32 { self.child_number }
+
+	/// Returns the chain code.
+	pub fn chain_code(&self) -> &[u8; 32] { &self.chain_code }
+
+	/// Returns the public key.
+	pub fn public_key(&self) -> &PublicKey { &self.key }
+
+	/// Returns the key identifier: Hash160 of the compressed public key.
+	pub fn identifier(&self) -> [u8; 20] {
+		hash160(&self.key.encode(PointFormat::Compressed))
+	}
+
+	/// Returns the fingerprint: the first 4 bytes of the identifier.
+	pub fn fingerprint(&self) -> [u8; 4] {
+		fingerprint(&self.identifier())
+	}
+
+	/// Derives the normal child key, the hardened children can't be derived from the public key.
+	///
+	/// Returns `InvalidKey` for the negligible part of the indices, the next index should be used then.
+	pub fn derive_child(&self, index: u32) -> Result<Self, Bip32Error> {
+		if index >= HARDENED {
+			return Err(Bip32Error::HardenedFromPublic);
+		}
+
+		let depth = self.depth.checked_add(1).ok_or(Bip32Error::MaxDepthExceeded)?;
+
+		let mut data = self.key.encode(PointFormat::Compressed);
+		data.extend_from_slice(&index.to_be_bytes());
+
+		// K_i = I_L * G + K, I_L should be less than n and K_i shouldn't be the point at infinity
+		let (tweak, chain_code) = split(&hmac_sha512(&self.chain_code, &data));
+		let tweak = Scalar::from_bytes(&tweak).ok_or(Bip32Error::InvalidKey)?;
+		let key = PublicKey::new(SECP256K1.mul_generator(&tweak) + *self.key.point()).map_err(|_| Bip32Error::InvalidKey)?;
+
+		Ok(ExtendedPubKey { depth, parent_fingerprint: self.fingerprint(), child_number: index, chain_code, key })
+	}
+
+	/// Derives the key by the path relative to this key, the path should contain only the normal indices.
+	pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Bip32Error> {
+		path.indices().iter().try_fold(*self, |key, &index| key.derive_child(index))
+	}
+
+	/// Serializes the key in Base58Check with the given version bytes (e.g. `XPUB_VERSION`).
+	pub fn encode(&self, version: u32) -> String {
+		let mut key = [0u8; 33];
+		key.copy_from_slice(&self.key.encode(PointFormat::Compressed));
+		encode(version, self.depth, &self.parent_fingerprint, self.child_number, &self.chain_code, &key)
+	}
+
+	/// Deserializes the key from Base58Check, checking the version bytes and the point.
+	pub fn decode(s: &str, version: u32) -> Result<Self, Bip32Error> {
+		let decoded = decode(s, version)?;
+
+		// Only the compressed point is allowed
+		if decoded.key[0] != 0x02 && decoded.key[0] != 0x03 {
+			return Err(Bip32Error::InvalidEncoding);
+		}
+
+		let key = PublicKey::decode(&decoded.key).map_err(Bip32Error::InvalidPoint)?;
+
+		Ok(ExtendedPubKey {
+			depth: decoded.depth,
+			parent_fingerprint: decoded.parent_fingerprint,
+			child_number: decoded.child_number,
+			chain_code: decoded.chain_code,
+			key
+		})
+	}
+}
+
+/// Path of the key derivation, e.g. `m/44'/0'/0'/0/5`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DerivationPath {
+	/// Child indices from the root, the hardened indices are not less than `HARDENED`.
+	indices: Vec<u32>
+}
+
+impl DerivationPath {
+	/// Creates the path from the child indices.
+	pub fn new(indices: Vec<u32>) -> Self {
+		DerivationPath { indices }
+	}
+
+	/// Returns the child indices.
+	pub fn indices(&self) -> &[u32] { &self.indices }
+}
+
+impl FromStr for DerivationPath {
+	type Err = Bip32Error;
+
+	/// Parses the path: `m` followed by the indices separated by `/`, the hardened index has `'` or `h` suffix.
+	fn from_str(s: &str) -> Result<Self, Bip32Error> {
+		let mut parts = s.split('/');
+		if parts.next() != Some("m") {
+			return Err(Bip32Error::InvalidPath);
+		}
+
+		let indices = parts.map(|part| {
+			let (num, hardened) = match part.strip_suffix(['\'', 'h']) {
+				Some(num) => (num, true),
+				None => (part, false)
+			};
+
+			// Only the plain decimal numbers are allowed, without the sign
+			if num.is_empty() || !num.bytes().all(|c| c.is_ascii_digit()) {
+				return Err(Bip32Error::InvalidPath);
+			}
+
+			match num.parse::<u32>() {
+				Ok(index) if index < HARDENED => Ok(if hardened { index | HARDENED } else { index }),
+				_ => Err(Bip32Error::InvalidPath)
+			}
+		}).collect::<Result<_, _>>()?;
+
+		Ok(DerivationPath { indices })
+	}
+}
+
+impl fmt::Display for DerivationPath {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		write!(f, "m")?;
+
+		for &index in &self.indices {
+			if index >= HARDENED {
+				write!(f, "/{}'", index - HARDENED)?;
+			} else {
+				write!(f, "/{}", index)?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Error of BIP32 key derivation and decoding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Bip32Error {
+	/// Seed isn't 16 to 64 bytes long.
+	InvalidSeedLength,
+
+	/// Derived or decoded private key isn't in the `[1; n)` range or the public key is the point at infinity.
+	InvalidKey,
+
+	/// Hardened child can't be derived from the public key.
+	HardenedFromPublic,
+
+	/// Depth of the derived key exceeds 255.
+	MaxDepthExceeded,
+
+	/// Derivation path has the wrong format.
+	InvalidPath,
+
+	/// Serialized key isn't valid Base58Check string.
+	Base58(Base58Error),
+
+	/// Serialized key has the unexpected version bytes.
+	InvalidVersion,
+
+	/// Serialized key has the wrong length or structure.
+	InvalidEncoding,
+
+	/// Serialized public key isn't the valid point.
+	InvalidPoint(PointError)
+}
+
+impl fmt::Display for Bip32Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			Bip32Error::InvalidSeedLength => write!(f, "Seed should be 16 to 64 bytes long."),
+			Bip32Error::InvalidKey => write!(f, "Derived key is invalid."),
+			Bip32Error::HardenedFromPublic => write!(f, "Hardened child can not be derived from the public key."),
+			Bip32Error::MaxDepthExceeded => write!(f, "Maximum derivation depth is exceeded."),
+			Bip32Error::InvalidPath => write!(f, "Derivation path is invalid."),
+			Bip32Error::Base58(err) => write!(f, "{}", err),
+			Bip32Error::InvalidVersion => write!(f, "Extended key version is unexpected."),
+			Bip32Error::InvalidEncoding => write!(f, "Extended key encoding is invalid."),
+			Bip32Error::InvalidPoint(err) => write!(f, "{}", err)
+		}
+	}
+}
+
+impl Error for Bip32Error { }
+
+impl From<Base58Error> for Bip32Error {
+	fn from(err: Base58Error) -> Self {
+		Bip32Error::Base58(err)
+	}
+}
+
+/// Splits HMAC-SHA512 output into the left half (the key or the tweak) and the right half (the chain code).
+fn split(bytes: &[u8; 64]) -> ([u8; 32], [u8; 32]) {
+	let (mut left, mut right) = ([0u8; 32], [0u8; 32]);
+	left.copy_from_slice(&bytes[..32]);
+	right.copy_from_slice(&bytes[32..]);
+	(left, right)
+}
+
+/// Returns the first 4 bytes.
+fn fingerprint(bytes: &[u8]) -> [u8; 4] {
+	let mut res = [0u8; 4];
+	res.copy_from_slice(&bytes[..4]);
+	res
+}
+
+/// Serializes the extended key: version, depth, parent fingerprint, child number, chain code and key.
+fn encode(version: u32, depth: u8, parent_fingerprint: &[u8; 4], child_number: u32, chain_code: &[u8; 32], key: &[u8; 33]) -> String {
+	let mut data = Vec::with_capacity(ENCODED_SIZE);
+	data.extend_from_slice(&version.to_be_bytes());
+	data.push(depth);
+	data.extend_from_slice(parent_fingerprint);
+	data.extend_from_slice(&child_number.to_be_bytes());
+	data.extend_from_slice(chain_code);
+	data.extend_from_slice(key);
+	base58::encode_check(&data)
+}
+
+/// Fields of the serialized extended key except the version.
+struct Decoded {
+	depth: u8,
+	parent_fingerprint: [u8; 4],
+	child_number: u32,
+	chain_code: [u8; 32],
+
+	/// The private key prefixed by the zero byte or the compressed public key.
+	key: [u8; 33]
+}
+
+/// Deserializes the extended key, checking the version bytes.
+fn decode(s: &str, version: u32) -> Result<Decoded, Bip32Error> {
+	let data = base58::decode_check(s)?;
+	if data.len() != ENCODED_SIZE {
+		return Err(Bip32Error::InvalidEncoding);
+	}
+
+	let read_u32 = |pos: usize| u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+	if read_u32(0) != version {
+		return Err(Bip32Error::InvalidVersion);
+	}
+
+	let depth = data[4];
+	let parent_fingerprint = fingerprint(&data[5..9]);
+	let child_number = read_u32(9);
+
+	// The master key has no parent
+	if depth == 0 && (parent_fingerprint != [0; 4] || child_number != 0) {
+		return Err(Bip32Error::InvalidEncoding);
+	}
+
+	let mut chain_code = [0u8; 32];
+	chain_code.copy_from_slice(&data[13..45]);
+	let mut key = [0u8; 33];
+	key.copy_from_slice(&data[45..]);
+
+	Ok(Decoded { depth, parent_fingerprint, child_number, chain_code, key })
+}
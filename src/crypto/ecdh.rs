@@ -0,0 +1,71 @@
+use crypto::field::PrimeField;
+use crypto::ecdsa::{Curve, Secp256k1, ECPoint, PrivateKey, PublicKey, PointError, PointFormat};
+use crypto::hash::sha256;
+use std::fmt;
+
+/// Shared secret of the elliptic curve Diffie-Hellman key agreement: the point `dQ`,
+/// where `d` is the own private key and `Q` is the public key of the peer.
+pub struct SharedSecret<C: Curve = Secp256k1> {
+	/// The shared point, it's never the point at infinity.
+	point: ECPoint<C>
+}
+
+impl<C: Curve> SharedSecret<C> {
+	/// Returns the `x` coordinate of the shared point as the big-endian bytes of the field length
+	/// (the shared secret value `z` of SEC 1, section 3.3.1).
+	///
+	/// The coordinate isn't uniformly distributed, so it should be passed through the key derivation function
+	/// before using as the key.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let len = C::Field::NUM_BITS.div_ceil(8);
+		self.point.x().to_bytes()[32 - len..].to_vec()
+	}
+
+	/// Returns SHA-256 of the compressed shared point, the same as `secp256k1_ecdh` with the default hash function.
+	pub fn hash(&self) -> [u8; 32] {
+		sha256(&self.point.encode(PointFormat::Compressed))
+	}
+
+	/// Returns the shared point.
+	pub fn point(&self) -> &ECPoint<C> { &self.point }
+}
+
+impl<C: Curve> Clone for SharedSecret<C> {
+	fn clone(&self) -> Self {
+		SharedSecret { point: self.point }
+	}
+}
+
+impl<C: Curve> PartialEq for SharedSecret<C> {
+	fn eq(&self, other: &Self) -> bool {
+		self.point == other.point
+	}
+}
+
+impl<C: Curve> fmt::Debug for SharedSecret<C> {
+	/// Doesn't output the secret point.
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		write!(f, "SharedSecret(..)")
+	}
+}
+
+/// Computes the shared secret of the own private key and the peer point.
+///
+/// The peer point is validated as the public key: it should be on the curve, in the subgroup
+/// of the generation point and not the point at infinity, otherwise the invalid curve
+/// and the small subgroup attacks would reveal the private key.
+/// The multiplication is done in the constant time.
+pub fn ecdh<C: Curve>(private: &PrivateKey<C>, public: &ECPoint<C>) -> Result<SharedSecret<C>, PointError> {
+	let public = PublicKey::new(*public)?;
+	let point = *public.point() * *private.scalar();
+
+	// The peer point has the prime order and the scalar isn't zero
+	debug_assert!(!point.is_identity());
+
+	Ok(SharedSecret { point })
+}
+
+/// Computes the shared secret and returns its hash (see `SharedSecret::hash`).
+pub fn ecdh_hashed<C: Curve>(private: &PrivateKey<C>, public: &ECPoint<C>) -> Result<[u8; 32], PointError> {
+	ecdh(private, public).map(|secret| secret.hash())
+}
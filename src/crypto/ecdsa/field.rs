@@ -0,0 +1,86 @@
+use utils::bignum::Uint256;
+use crypto::field::{FieldModulus, Fp};
+
+/// The modulo of the secp256k1 base field (`p`).
+pub const SECP256K1_P: Uint256 = Uint256([0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]);
+
+/// The order of the secp256k1 generation point (`n`).
+pub const SECP256K1_N: Uint256 = Uint256([0xbfd25e8cd0364141, 0xbaaedce6af48a03b, 0xfffffffffffffffe, 0xffffffffffffffff]);
+
+/// The modulo of the secp256k1 base field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp256k1Base;
+
+impl FieldModulus for Secp256k1Base {
+	const MODULO: Uint256 = SECP256K1_P;
+}
+
+/// The modulo of the secp256k1 scalar field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp256k1Order;
+
+impl FieldModulus for Secp256k1Order {
+	const MODULO: Uint256 = SECP256K1_N;
+}
+
+/// Element of the secp256k1 base field (coordinate of the curve point).
+pub type FieldElement = Fp<Secp256k1Base>;
+
+/// Element of the secp256k1 scalar field (multiplier of the curve point).
+pub type Scalar = Fp<Secp256k1Order>;
+
+/// The modulo of the secp256r1 (P-256) base field (`p`).
+pub const SECP256R1_P: Uint256 = Uint256([0xffffffffffffffff, 0x00000000ffffffff, 0x0000000000000000, 0xffffffff00000001]);
+
+/// The order of the secp256r1 (P-256) generation point (`n`).
+pub const SECP256R1_N: Uint256 = Uint256([0xf3b9cac2fc632551, 0xbce6faada7179e84, 0xffffffffffffffff, 0xffffffff00000000]);
+
+/// The modulo of the secp256r1 base field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp256r1Base;
+
+impl FieldModulus for Secp256r1Base {
+	const MODULO: Uint256 = SECP256R1_P;
+}
+
+/// The modulo of the secp256r1 scalar field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp256r1Order;
+
+impl FieldModulus for Secp256r1Order {
+	const MODULO: Uint256 = SECP256R1_N;
+}
+
+/// Element of the secp256r1 base field.
+pub type Secp256r1FieldElement = Fp<Secp256r1Base>;
+
+/// Element of the secp256r1 scalar field.
+pub type Secp256r1Scalar = Fp<Secp256r1Order>;
+
+/// The modulo of the secp224k1 base field (`p`).
+pub const SECP224K1_P: Uint256 = Uint256([0xfffffffeffffe56d, 0xffffffffffffffff, 0xffffffffffffffff, 0x00000000ffffffff]);
+
+/// The order of the secp224k1 generation point (`n`), it's greater than `p`.
+pub const SECP224K1_N: Uint256 = Uint256([0xcaf0a971769fb1f7, 0x0001dce8d2ec6184, 0x0000000000000000, 0x0000000100000000]);
+
+/// The modulo of the secp224k1 base field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp224k1Base;
+
+impl FieldModulus for Secp224k1Base {
+	const MODULO: Uint256 = SECP224K1_P;
+}
+
+/// The modulo of the secp224k1 scalar field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp224k1Order;
+
+impl FieldModulus for Secp224k1Order {
+	const MODULO: Uint256 = SECP224K1_N;
+}
+
+/// Element of the secp224k1 base field.
+pub type Secp224k1FieldElement = Fp<Secp224k1Base>;
+
+/// Element of the secp224k1 scalar field.
+pub type Secp224k1Scalar = Fp<Secp224k1Order>;
@@ -0,0 +1,109 @@
+use utils::bignum::{Uint256, Uint512};
+use crypto::field::PrimeField;
+
+/// Efficiently computable endomorphism `(x, y) -> (beta * x, y)` of the curve, which acts
+/// on the points of the prime order subgroup as the multiplication by `lambda`.
+///
+/// Used to split the scalar into two halves of the half length (GLV method).
+pub(crate) struct Endomorphism<F, S> {
+	/// Cube root of unity in the coordinate field.
+	pub beta: F,
+
+	/// Cube root of unity in the scalar field, `lambda * (x, y) = (beta * x, y)`.
+	pub lambda: S,
+
+	/// `round(2^384 * b2 / n)`, where `(a1, b1)` and `(a2, b2)` are the short basis of the lattice
+	/// `{(x, y): x + y * lambda = 0 (mod n)}`.
+	pub g1: Uint256,
+
+	/// `round(2^384 * (-b1) / n)`.
+	pub g2: Uint256,
+
+	/// `-b1 (mod n)`.
+	pub minus_b1: S,
+
+	/// `-b2 (mod n)`.
+	pub minus_b2: S
+}
+
+impl<F: PrimeField, S: PrimeField> Endomorphism<F, S> {
+	/// Returns `(k1, k2)` such that `k = k1 + k2 * lambda (mod n)` and `k1`, `k2` are about
+	/// a half of the scalar length by the absolute value.
+	pub fn decompose(&self, k: &S) -> (S, S) {
+		let k_uint = k.to_uint();
+
+		// c1 = round(k * b2 / n), c2 = round(k * (-b1) / n)
+		let c1 = S::reduce(&mul_shift_384(&k_uint, &self.g1));
+		let c2 = S::reduce(&mul_shift_384(&k_uint, &self.g2));
+
+		// k2 = -(c1 * b1 + c2 * b2), k1 = k - k2 * lambda
+		let k2 = c1 * self.minus_b1 + c2 * self.minus_b2;
+		let k1 = *k - k2 * self.lambda;
+
+		(k1, k2)
+	}
+
+	/// Splits the term `k * (x, y)` into two terms with the short positive scalars:
+	/// `k1 * (x, y) + k2 * (beta * x, y)`, the negative halves negate the points.
+	pub fn split(&self, k: &S, x: &F, y: &F) -> [(Uint256, (F, F)); 2] {
+		let (k1, k2) = self.decompose(k);
+		let half = S::modulo() / 2;
+		let term = |k: S, x: F| {
+			if k.to_uint() > half {
+				((-k).to_uint(), (x, -*y))
+			} else {
+				(k.to_uint(), (x, *y))
+			}
+		};
+
+		[term(k1, *x), term(k2, self.beta * *x)]
+	}
+}
+
+/// Returns `round(a * b / 2^384)`.
+fn mul_shift_384(a: &Uint256, b: &Uint256) -> Uint256 {
+	let prod = Uint512::from(*a) * Uint512::from(*b);
+	let raw = prod.raw();
+
+	// Round by the most significant dropped bit
+	let round = raw[5] >> 63;
+	Uint256::from_raw([raw[6], raw[7], 0, 0]) + Uint256::from(round)
+}
+
+#[cfg(test)]
+use super::{SECP256K1, Scalar};
+#[cfg(test)]
+use utils::bignum::{Zero, One};
+
+#[test]
+fn test_mul_shift_384() {
+	let a = Uint256::from_raw([0, 0, 0, 1 << 63]);
+
+	// 2^255 * 2^130 / 2^384 = 2, 2^255 * 2^128 / 2^384 = 0.5, 2^255 * 2^127 / 2^384 = 0.25
+	assert_eq!(mul_shift_384(&a, &Uint256::from_raw([0, 0, 4, 0])), Uint256::from(2));
+	assert_eq!(mul_shift_384(&a, &Uint256::from_raw([0, 0, 1, 0])), Uint256::one());
+	assert_eq!(mul_shift_384(&a, &Uint256::from_raw([0, 1 << 63, 0, 0])), Uint256::zero());
+}
+
+#[test]
+fn test_decompose() {
+	let endo = SECP256K1.endomorphism.as_ref().unwrap();
+	let scalars = [
+		Scalar::zero(),
+		Scalar::one(),
+		-Scalar::one(),
+		endo.lambda,
+		Scalar::reduce(&Uint256::from_raw([0x8ee3c2613bf99522, 0x624d4cc0fa157859, 0xa65527f7290311a3, 0xaa5e28d6a97a2479])),
+		Scalar::reduce(&Uint256::from_raw([0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0x7fffffffffffffff]))
+	];
+
+	for k in scalars.iter() {
+		let (k1, k2) = endo.decompose(k);
+		assert_eq!(k1 + k2 * endo.lambda, *k);
+
+		let g = SECP256K1.gen_point();
+		for (half, _) in endo.split(k, g.x(), g.y()).iter() {
+			assert!(half.bits() <= 128);
+		}
+	}
+}
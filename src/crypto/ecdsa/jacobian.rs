@@ -0,0 +1,321 @@
+use crypto::field::PrimeField;
+
+/// Point on the elliptic curve in the Jacobian projective coordinates.
+///
+/// Represents the affine point `(X / Z^2, Y / Z^3)`, the point at infinity has `Z = 0`.
+/// Doubling and addition don't need the modular inversion, it's performed only once
+/// when the point is converted back into the affine coordinates.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct JacobianPoint<F> {
+	pub x: F,
+	pub y: F,
+	pub z: F
+}
+
+impl<F: PrimeField> JacobianPoint<F> {
+	/// Returns the point at infinity.
+	pub fn identity() -> Self {
+		JacobianPoint { x: F::one(), y: F::one(), z: F::zero() }
+	}
+
+	/// Creates point from the affine coordinates.
+	pub fn from_affine(x: F, y: F) -> Self {
+		JacobianPoint { x, y, z: F::one() }
+	}
+
+	/// Returns `true` if the point is the point at infinity.
+	pub fn is_identity(&self) -> bool {
+		self.z.is_zero()
+	}
+
+	/// Converts the point into the affine coordinates, returns `None` for the point at infinity.
+	pub fn to_affine(self) -> Option<(F, F)> {
+		let zinv = self.z.invert()?;
+		let zinv2 = zinv.square();
+
+		Some((self.x * zinv2, self.y * zinv2 * zinv))
+	}
+
+	/// Converts the points into the affine coordinates using the single modular inversion
+	/// (Montgomery's trick), returns `None` for the points at infinity.
+	pub fn batch_to_affine(points: &[Self]) -> Vec<Option<(F, F)>> {
+		// acc[i] = Z0 * Z1 * ... * Zi, the points at infinity are skipped
+		let mut acc = Vec::with_capacity(points.len());
+		let mut prod = F::one();
+		for p in points {
+			if !p.is_identity() {
+				prod = prod * p.z;
+			}
+			acc.push(prod);
+		}
+
+		// The product of the non-zero elements of the prime field is non-zero
+		let mut inv = prod.invert().expect("The product should be invertible.");
+		let mut res = vec![None; points.len()];
+
+		for i in (0..points.len()).rev() {
+			let p = &points[i];
+			if p.is_identity() {
+				continue;
+			}
+
+			// Zi^(-1) = (Z0 * ... * Zi)^(-1) * (Z0 * ... * Z(i-1))
+			let zinv = if i == 0 { inv } else { inv * acc[i - 1] };
+			inv = inv * p.z;
+
+			let zinv2 = zinv.square();
+			res[i] = Some((p.x * zinv2, p.y * zinv2 * zinv));
+		}
+
+		res
+	}
+
+	/// Returns `a` if `choice` is `false` and `b` if `choice` is `true`, without branching on `choice`.
+	pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+		JacobianPoint {
+			x: F::conditional_select(&a.x, &b.x, choice),
+			y: F::conditional_select(&a.y, &b.y, choice),
+			z: F::conditional_select(&a.z, &b.z, choice)
+		}
+	}
+
+	/// Swaps `a` and `b` if `choice` is `true`, without branching on `choice`.
+	pub fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+		let (na, nb) = (Self::conditional_select(a, b, choice), Self::conditional_select(b, a, choice));
+		*a = na;
+		*b = nb;
+	}
+
+	/// Doubles the point, `a` is the curve parameter ("dbl-2007-bl" formulas).
+	///
+	/// Doesn't branch on the point: if `Z1 = 0` or `Y1 = 0` then `Z3 = 2 * Y1 * Z1 = 0`.
+	pub fn double(&self, a: &F) -> Self {
+		let xx = self.x.square();									// XX = X1^2
+		let yy = self.y.square();									// YY = Y1^2
+		let yyyy = yy.square();										// YYYY = YY^2
+		let zz = self.z.square();									// ZZ = Z1^2
+		let s = double((self.x + yy).square() - xx - yyyy);			// S = 2 * ((X1 + YY)^2 - XX - YYYY)
+		let mut m = double(xx) + xx;								// M = 3 * XX + a * ZZ^2
+		if !a.is_zero() {
+			m = m + *a * zz.square();
+		}
+
+		let x3 = m.square() - double(s);							// X3 = M^2 - 2 * S
+		let y3 = m * (s - x3) - double(double(double(yyyy)));		// Y3 = M * (S - X3) - 8 * YYYY
+		let z3 = (self.y + self.z).square() - yy - zz;				// Z3 = (Y1 + Z1)^2 - YY - ZZ
+
+		JacobianPoint { x: x3, y: y3, z: z3 }
+	}
+
+	/// Adds two points, `a` is the curve parameter ("add-2007-bl" formulas).
+	pub fn add(&self, other: &Self, a: &F) -> Self {
+		if self.is_identity() {
+			return *other;
+		} else if other.is_identity() {
+			return *self;
+		}
+
+		let z1z1 = self.z.square();									// Z1Z1 = Z1^2
+		let z2z2 = other.z.square();								// Z2Z2 = Z2^2
+		let u1 = self.x * z2z2;										// U1 = X1 * Z2Z2
+		let u2 = other.x * z1z1;									// U2 = X2 * Z1Z1
+		let s1 = self.y * other.z * z2z2;							// S1 = Y1 * Z2 * Z2Z2
+		let s2 = other.y * self.z * z1z1;							// S2 = Y2 * Z1 * Z1Z1
+		let h = u2 - u1;											// H = U2 - U1
+		let r = double(s2 - s1);									// r = 2 * (S2 - S1)
+
+		if h.is_zero() {
+			// P + P = 2P, P + (-P) = O
+			return if r.is_zero() { self.double(a) } else { Self::identity() };
+		}
+
+		let i = double(h).square();									// I = (2 * H)^2
+		let j = h * i;												// J = H * I
+		let v = u1 * i;												// V = U1 * I
+		let x3 = r.square() - j - double(v);						// X3 = r^2 - J - 2 * V
+		let y3 = r * (v - x3) - double(s1 * j);						// Y3 = r * (V - X3) - 2 * S1 * J
+		let z3 = ((self.z + other.z).square() - z1z1 - z2z2) * h;	// Z3 = ((Z1 + Z2)^2 - Z1Z1 - Z2Z2) * H
+
+		JacobianPoint { x: x3, y: y3, z: z3 }
+	}
+
+	/// Adds two points without branching on them, `a` is the curve parameter.
+	///
+	/// Both the sum and the doubling are computed, the result is selected in the constant time.
+	pub fn add_ct(&self, other: &Self, a: &F) -> Self {
+		let z1z1 = self.z.square();									// Z1Z1 = Z1^2
+		let z2z2 = other.z.square();								// Z2Z2 = Z2^2
+		let u1 = self.x * z2z2;										// U1 = X1 * Z2Z2
+		let u2 = other.x * z1z1;									// U2 = X2 * Z1Z1
+		let s1 = self.y * other.z * z2z2;							// S1 = Y1 * Z2 * Z2Z2
+		let s2 = other.y * self.z * z1z1;							// S2 = Y2 * Z1 * Z1Z1
+		let h = u2 - u1;											// H = U2 - U1
+		let r = double(s2 - s1);									// r = 2 * (S2 - S1)
+		let i = double(h).square();									// I = (2 * H)^2
+		let j = h * i;												// J = H * I
+		let v = u1 * i;												// V = U1 * I
+		let x3 = r.square() - j - double(v);						// X3 = r^2 - J - 2 * V
+		let y3 = r * (v - x3) - double(s1 * j);						// Y3 = r * (V - X3) - 2 * S1 * J
+		let z3 = ((self.z + other.z).square() - z1z1 - z2z2) * h;	// Z3 = ((Z1 + Z2)^2 - Z1Z1 - Z2Z2) * H
+
+		// P + (-P) = O is handled by the formulas: H = 0 => Z3 = 0
+		let mut res = JacobianPoint { x: x3, y: y3, z: z3 };
+		res = Self::conditional_select(&res, &self.double(a), h.is_zero() & r.is_zero());
+		res = Self::conditional_select(&res, other, self.is_identity());
+		Self::conditional_select(&res, self, other.is_identity())
+	}
+
+	/// Adds the affine point `(x, y)` without branching on the points ("madd-2007-bl" formulas).
+	///
+	/// The affine point shouldn't be equal to this point or its negation, because the doubling
+	/// isn't computed. The point at infinity is handled.
+	pub fn add_affine_ct(&self, x: &F, y: &F) -> Self {
+		let z1z1 = self.z.square();									// Z1Z1 = Z1^2
+		let u2 = *x * z1z1;											// U2 = X2 * Z1Z1
+		let s2 = *y * self.z * z1z1;								// S2 = Y2 * Z1 * Z1Z1
+		let h = u2 - self.x;										// H = U2 - X1
+		let r = double(s2 - self.y);								// r = 2 * (S2 - Y1)
+		let hh = h.square();										// HH = H^2
+		let i = double(double(hh));									// I = 4 * HH
+		let j = h * i;												// J = H * I
+		let v = self.x * i;											// V = X1 * I
+		let x3 = r.square() - j - double(v);						// X3 = r^2 - J - 2 * V
+		let y3 = r * (v - x3) - double(self.y * j);					// Y3 = r * (V - X3) - 2 * Y1 * J
+		let z3 = (self.z + h).square() - z1z1 - hh;					// Z3 = (Z1 + H)^2 - Z1Z1 - HH
+
+		let res = JacobianPoint { x: x3, y: y3, z: z3 };
+		Self::conditional_select(&res, &Self::from_affine(*x, *y), self.is_identity())
+	}
+
+	/// Adds the affine point `(x, y)`, `a` is the curve parameter ("madd-2007-bl" formulas).
+	pub fn add_affine(&self, x: &F, y: &F, a: &F) -> Self {
+		if self.is_identity() {
+			return Self::from_affine(*x, *y);
+		}
+
+		let z1z1 = self.z.square();									// Z1Z1 = Z1^2
+		let u2 = *x * z1z1;											// U2 = X2 * Z1Z1
+		let s2 = *y * self.z * z1z1;								// S2 = Y2 * Z1 * Z1Z1
+		let h = u2 - self.x;										// H = U2 - X1
+		let r = double(s2 - self.y);								// r = 2 * (S2 - Y1)
+
+		if h.is_zero() {
+			// P + P = 2P, P + (-P) = O
+			return if r.is_zero() { self.double(a) } else { Self::identity() };
+		}
+
+		let hh = h.square();										// HH = H^2
+		let i = double(double(hh));									// I = 4 * HH
+		let j = h * i;												// J = H * I
+		let v = self.x * i;											// V = X1 * I
+		let x3 = r.square() - j - double(v);						// X3 = r^2 - J - 2 * V
+		let y3 = r * (v - x3) - double(self.y * j);					// Y3 = r * (V - X3) - 2 * Y1 * J
+		let z3 = (self.z + h).square() - z1z1 - hh;					// Z3 = (Z1 + H)^2 - Z1Z1 - HH
+
+		JacobianPoint { x: x3, y: y3, z: z3 }
+	}
+}
+
+/// Returns `2a`.
+fn double<F: PrimeField>(a: F) -> F {
+	a + a
+}
+
+#[cfg(test)]
+use super::{SECP256K1, ECPoint, FieldElement};
+
+#[test]
+fn test_double() {
+	let g = SECP256K1.gen_point();
+	let a = *SECP256K1.a();
+	let mut p = g.to_jacobian();
+
+	for _ in 0..5 {
+		p = p.double(&a);
+	}
+
+	assert_eq!(ECPoint::from_jacobian(&p), g.double().double().double().double().double());
+	assert!(JacobianPoint::<FieldElement>::identity().double(&a).is_identity());
+}
+
+#[test]
+fn test_batch_to_affine() {
+	let g = SECP256K1.gen_point();
+	let a = *SECP256K1.a();
+	let p2 = g.to_jacobian().double(&a);
+	let points = [p2, JacobianPoint::identity(), p2.double(&a), g.to_jacobian()];
+	let affine = JacobianPoint::batch_to_affine(&points);
+
+	for (p, q) in points.iter().zip(affine.iter()) {
+		assert_eq!(p.to_affine(), *q);
+	}
+	assert!(JacobianPoint::<FieldElement>::batch_to_affine(&[]).is_empty());
+}
+
+#[test]
+fn test_add_ct() {
+	let g = SECP256K1.gen_point();
+	let a = *SECP256K1.a();
+	let o = JacobianPoint::identity();
+	let p2 = g.to_jacobian().double(&a);
+	let p4 = p2.double(&a);
+
+	assert_eq!(ECPoint::from_jacobian(&p2.add_ct(&p4, &a)), g.double() + g.double().double());
+	assert_eq!(ECPoint::from_jacobian(&p2.add_ct(&p2, &a)), g.double().double());
+	assert!(p2.add_ct(&(-g.double()).to_jacobian(), &a).is_identity());
+	assert_eq!(ECPoint::from_jacobian(&o.add_ct(&p2, &a)), g.double());
+	assert_eq!(ECPoint::from_jacobian(&p2.add_ct(&o, &a)), g.double());
+	assert!(o.add_ct(&o, &a).is_identity());
+}
+
+#[test]
+fn test_add_affine_ct() {
+	let g = SECP256K1.gen_point();
+	let a = *SECP256K1.a();
+	let p2 = g.to_jacobian().double(&a);
+
+	assert_eq!(ECPoint::from_jacobian(&p2.add_affine_ct(g.x(), g.y())), g.double() + g);
+	assert_eq!(ECPoint::from_jacobian(&JacobianPoint::identity().add_affine_ct(g.x(), g.y())), g);
+}
+
+#[test]
+fn test_conditional_swap() {
+	let g = SECP256K1.gen_point();
+	let (mut p, mut q) = (g.to_jacobian(), JacobianPoint::identity());
+
+	JacobianPoint::conditional_swap(&mut p, &mut q, false);
+	assert!(q.is_identity() && !p.is_identity());
+
+	JacobianPoint::conditional_swap(&mut p, &mut q, true);
+	assert!(p.is_identity());
+	assert_eq!(ECPoint::from_jacobian(&q), g);
+}
+
+#[test]
+fn test_add() {
+	let g = SECP256K1.gen_point();
+	let a = *SECP256K1.a();
+
+	// Both points have Z != 1
+	let p2 = g.to_jacobian().double(&a);
+	let p4 = p2.double(&a);
+	let p6 = p2.add(&p4, &a);
+
+	assert_eq!(ECPoint::from_jacobian(&p6), g.double() + g.double().double());
+	assert_eq!(ECPoint::from_jacobian(&p2.add(&p2, &a)), g.double().double());
+	assert!(p2.add(&(-g.double()).to_jacobian(), &a).is_identity());
+	assert_eq!(ECPoint::from_jacobian(&JacobianPoint::identity().add(&p2, &a)), g.double());
+	assert_eq!(ECPoint::from_jacobian(&p2.add(&JacobianPoint::identity(), &a)), g.double());
+}
+
+#[test]
+fn test_add_affine() {
+	let g = SECP256K1.gen_point();
+	let a = *SECP256K1.a();
+	let p2 = g.to_jacobian().double(&a);
+
+	assert_eq!(ECPoint::from_jacobian(&p2.add_affine(g.x(), g.y(), &a)), g.double() + g);
+	assert_eq!(ECPoint::from_jacobian(&g.to_jacobian().add_affine(g.x(), g.y(), &a)), g.double());
+	assert!((-g).to_jacobian().add_affine(g.x(), g.y(), &a).is_identity());
+	assert_eq!(ECPoint::from_jacobian(&JacobianPoint::identity().add_affine(g.x(), g.y(), &a)), g);
+}
@@ -1,294 +1,687 @@
-use utils::bignum::{Int512, Uint512, Uint256, Zero, One};
-use std::ops::{Add, Mul};
+mod field;
+mod jacobian;
+mod msm;
+mod glv;
+mod signature;
+mod rfc6979;
+mod sec1;
+
+pub use self::field::{FieldElement, Scalar, Secp256k1Base, Secp256k1Order, SECP256K1_P, SECP256K1_N};
+pub use self::field::{Secp256r1FieldElement, Secp256r1Scalar, Secp256r1Base, Secp256r1Order, SECP256R1_P, SECP256R1_N};
+pub use self::field::{Secp224k1FieldElement, Secp224k1Scalar, Secp224k1Base, Secp224k1Order, SECP224K1_P, SECP224K1_N};
+pub use self::signature::{PrivateKey, PublicKey, Signature, RecoverableSignature, SignatureError};
+pub use self::sec1::PointFormat;
+
+use utils::bignum::{Uint256, Uint512, Zero, One};
+use crypto::field::{PrimeField, is_prime_modulo};
+use crypto::hash::{Hasher, Sha256};
+use self::jacobian::JacobianPoint;
+use self::glv::Endomorphism;
+use std::ops::{Add, Sub, Mul, Neg};
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+use std::error::Error;
 use std::fmt;
 
-/// Elliptic curve over finite field.
-pub struct EllipticCurve<'a> {
-	/// The modulo that defines finite field.
-	modulo: Uint256,
+/// Marker type of the elliptic curve, which gives the access to its parameters.
+///
+/// Points and keys are parametrized by the marker instead of borrowing the curve, so they don't store
+/// the curve reference and the points of the different curves have the different types.
+/// The marker of the curve defined at the runtime is made by `runtime_curve!`.
+pub trait Curve: 'static + Copy + Eq + fmt::Debug + Send + Sync {
+	/// The field of the point coordinates.
+	type Field: PrimeField + Send + Sync;
 
+	/// The field of the point multipliers, its modulo is the order of the generation point.
+	type Scalar: PrimeField + Send + Sync;
+
+	/// Returns the parameters of the curve.
+	fn curve() -> &'static EllipticCurve<Self>;
+}
+
+/// Defines the marker of the curve, which parameters are registered at the runtime.
+///
+/// The marker gets `register`, which validates the parameters by `EllipticCurve::new` and stores them,
+/// and `is_registered`. The curve can be registered only once, `Curve::curve` panics before that.
+///
+/// ```ignore
+/// runtime_curve!(pub MyCurve(FieldElement, Scalar));
+///
+/// let curve = MyCurve::register(a, b, gen_x, gen_y, 1)?;
+/// ```
+#[macro_export]
+macro_rules! runtime_curve {
+	($(#[$attr:meta])* $vis:vis $name:ident($field:ty, $scalar:ty)) => {
+		$(#[$attr])*
+		#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+		$vis struct $name;
+
+		impl $name {
+			/// Returns the storage of the curve parameters.
+			fn cell() -> &'static ::std::sync::OnceLock<$crate::crypto::ecdsa::EllipticCurve<$name>> {
+				static CURVE: ::std::sync::OnceLock<$crate::crypto::ecdsa::EllipticCurve<$name>> = ::std::sync::OnceLock::new();
+				&CURVE
+			}
+
+			/// Validates the parameters of the curve by `EllipticCurve::new` and registers them.
+			///
+			/// Returns `AlreadyRegistered` if the curve is registered, its parameters can't be changed.
+			pub fn register(a: $field, b: $field, gen_x: $field, gen_y: $field, cofactor: u64)
+				-> Result<&'static $crate::crypto::ecdsa::EllipticCurve<$name>, $crate::crypto::ecdsa::CurveError>
+			{
+				let curve = $crate::crypto::ecdsa::EllipticCurve::new(a, b, gen_x, gen_y, cofactor)?;
+				Self::cell().set(curve).map_err(|_| $crate::crypto::ecdsa::CurveError::AlreadyRegistered)?;
+				Ok(<Self as $crate::crypto::ecdsa::Curve>::curve())
+			}
+
+			/// Returns `true` if the parameters of the curve are registered.
+			pub fn is_registered() -> bool {
+				Self::cell().get().is_some()
+			}
+		}
+
+		impl $crate::crypto::ecdsa::Curve for $name {
+			type Field = $field;
+			type Scalar = $scalar;
+
+			fn curve() -> &'static $crate::crypto::ecdsa::EllipticCurve<Self> {
+				Self::cell().get().expect("The curve should be registered before use.")
+			}
+		}
+	}
+}
+
+/// Parameters of the elliptic curve over finite field.
+pub struct EllipticCurve<C: Curve = Secp256k1> {
 	/// The `a` and `b` curve parameters.
-	params: (Uint256, Uint256),
+	params: (C::Field, C::Field),
+
+	/// Coordinates of the generation point.
+	gen_point: (C::Field, C::Field),
+
+	/// The ratio between the number of the curve points and the order of the generation point.
+	cofactor: u64,
 
-	/// Generation point.
-	gen_point: ECPoint<'a>
+	/// Multiples of the generation point for `mul_generator`, built on the first use.
+	gen_table: OnceLock<GenTable<C::Field>>,
+
+	/// Endomorphism that speeds up the variable time multiplication, if the curve has one.
+	endomorphism: Option<Endomorphism<C::Field, C::Scalar>>
+}
+
+/// Width of the window of the generation point table in bits.
+const GEN_WINDOW: usize = 4;
+
+/// Number of the Miller-Rabin rounds of the moduli validation, the error probability is at most `4^(-64)`.
+const PRIMALITY_ROUNDS: u64 = 64;
+
+/// Returns the Miller-Rabin bases of the modulo: the hashes of the modulo and the round number,
+/// so they can't be chosen together with the modulo.
+fn primality_bases(modulo: &Uint256) -> Vec<Uint256> {
+	(0..PRIMALITY_ROUNDS).map(|i| {
+		let mut hasher = Sha256::new();
+		hasher.update(&modulo.to_be_bytes());
+		hasher.update(&i.to_be_bytes());
+		Uint256::from_be_bytes(&hasher.finalize())
+	}).collect()
 }
 
-impl<'a> EllipticCurve<'a> {
-	pub fn modulo(&self) -> &Uint256 {
-		&self.modulo
+/// Table of the generation point multiples: `table[i][j - 1] = j * 2^(4i) * G` for `j` in `[1; 16)`.
+type GenTable<F> = Vec<[(F, F); (1 << GEN_WINDOW) - 1]>;
+
+impl<C: Curve> EllipticCurve<C> {
+	/// Creates the curve `y^2 = x^3 + ax + b` over the field `C::Field` with the generation point `(gen_x, gen_y)`,
+	/// which order `n` is the modulo of `C::Scalar`, and the cofactor `h`.
+	///
+	/// Validates the parameters: both moduli are prime, the curve isn't singular, the generation point
+	/// is on the curve and has the order `n`, and `h * n` is the number of the curve points within the Hasse bound.
+	///
+	/// Only `a`, `b`, the generation point and the cofactor are the runtime values, the moduli `p` and `n`
+	/// are the constants of the field types (see `FieldModulus`). The curve of the marker made by `runtime_curve!`
+	/// is created and registered by its `register`.
+	pub fn new(a: C::Field, b: C::Field, gen_x: C::Field, gen_y: C::Field, cofactor: u64) -> Result<Self, CurveError> {
+		let p = C::Field::modulo();
+		let n = C::Scalar::modulo();
+
+		if p <= Uint256::from(3) || !is_prime_modulo::<C::Field>(&primality_bases(&p)) {
+			return Err(CurveError::ModuloNotPrime);
+		}
+
+		if !is_prime_modulo::<C::Scalar>(&primality_bases(&n)) {
+			return Err(CurveError::OrderNotPrime);
+		}
+
+		// The discriminant is -16 * (4a^3 + 27b^2)
+		let four = C::Field::from_uint(&Uint256::from(4)).expect("The modulo is greater than 3.");
+		let twenty_seven = C::Field::reduce(&Uint256::from(27));
+		if (four * a.square() * a + twenty_seven * b.square()).is_zero() {
+			return Err(CurveError::SingularCurve);
+		}
+
+		// n > 4 * sqrt(p), otherwise the number of the points doesn't define the cofactor
+		let (p, n) = (Uint512::from(p), Uint512::from(n));
+		if n * n <= p * Uint512::from(16) {
+			return Err(CurveError::OrderTooSmall);
+		}
+
+		// Hasse bound: |h * n - (p + 1)| <= 2 * sqrt(p)
+		let points = n * Uint512::from(cofactor);
+		let p1 = p + Uint512::one();
+		let diff = if points > p1 { points - p1 } else { p1 - points };
+		if cofactor == 0 || diff.bits() > 256 || diff * diff > p * Uint512::from(4) {
+			return Err(CurveError::InvalidCofactor);
+		}
+
+		let curve = EllipticCurve {
+			params: (a, b),
+			gen_point: (gen_x, gen_y),
+			cofactor,
+			gen_table: OnceLock::new(),
+			endomorphism: None
+		};
+
+		if !curve.contains(&gen_x, &gen_y) {
+			return Err(CurveError::GeneratorNotOnCurve);
+		}
+
+		// (n - 1)G = -G, the order of the point divides the prime `n`, so it's exactly `n`
+		let gen_point = curve.gen_point();
+		if curve.multi_mul(&[(-C::Scalar::one(), gen_point)]) != -gen_point {
+			return Err(CurveError::InvalidGeneratorOrder);
+		}
+
+		Ok(curve)
+	}
+
+	/// Returns the modulo that defines finite field.
+	pub fn modulo(&self) -> Uint256 {
+		C::Field::modulo()
 	}
 
-	pub fn a(&self) -> &Uint256 {
+	pub fn a(&self) -> &C::Field {
 		&self.params.0
 	}
 
-	pub fn b(&self) -> &Uint256 {
+	pub fn b(&self) -> &C::Field {
 		&self.params.1
 	}
 
-	pub fn gen_point(&self) -> &ECPoint {
-		&self.gen_point
+	pub fn gen_point(&self) -> ECPoint<C> {
+		ECPoint::new(self.gen_point.0, self.gen_point.1)
+	}
+
+	/// Returns the order of the generation point (`n`).
+	pub fn order(&self) -> Uint256 {
+		C::Scalar::modulo()
+	}
+
+	/// Returns the cofactor of the curve (`h`).
+	pub fn cofactor(&self) -> u64 {
+		self.cofactor
 	}
 
 	/// Creates point on this curve.
-	pub fn create_point(&self, x: Uint256, y: Uint256) -> ECPoint {
-		ECPoint { x, y, curve: self }
+	///
+	/// Coordinates aren't validated, use `try_create_point` for the untrusted input.
+	pub fn create_point(&self, x: C::Field, y: C::Field) -> ECPoint<C> {
+		ECPoint::new(x, y)
+	}
+
+	/// Returns the point at infinity (identity element of the curve group).
+	pub fn identity(&self) -> ECPoint<C> {
+		ECPoint::identity()
 	}
 
-	pub fn try_create_point(&self, x: Uint256, y: Uint256) -> Option<ECPoint> {
+	/// Creates point on this curve, checking that it satisfies the curve equation
+	/// and belongs to the subgroup generated by the generation point.
+	pub fn try_create_point(&self, x: Uint256, y: Uint256) -> Result<ECPoint<C>, PointError> {
+		let (x, y) = match (C::Field::from_uint(&x), C::Field::from_uint(&y)) {
+			(Some(x), Some(y)) => (x, y),
+			_ => return Err(PointError::CoordinateOutOfRange)
+		};
+
+		if !self.contains(&x, &y) {
+			return Err(PointError::NotOnCurve);
+		}
+
+		// Every point belongs to the subgroup if the curve order is prime
+		let point = self.create_point(x, y);
+		if self.cofactor != 1 && self.multi_mul(&[(-C::Scalar::one(), point)]) != -point {
+			return Err(PointError::NotInSubgroup);
+		}
 
-		unimplemented!() // TODO: implement
+		Ok(point)
 	}
-}
 
-/// Secp256k1.
-pub static SECP256K1: EllipticCurve<'static> = EllipticCurve {
-	modulo: Uint256([0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]),
-	params: (Uint256([0; 4]), Uint256([7, 0, 0, 0])),
-	gen_point: ECPoint {
-		x: Uint256([0x59f2815b16f81798, 0x029bfcdb2dce28d9, 0x55a06295ce870b07, 0x79be667ef9dcbbac]),
-		y: Uint256([0x9c47d08ffb10d4b8, 0xfd17b448a6855419, 0x5da4fbfc0e1108a8, 0x483ada7726a3c465]),
-		curve: &SECP256K1
+	/// Multiplies the generation point by the scalar in the constant time.
+	///
+	/// Uses the precomputed table of the generation point multiples, which is built on the first call.
+	/// Much faster than the multiplication of `gen_point()` by the scalar.
+	pub fn mul_generator(&self, k: &C::Scalar) -> ECPoint<C> {
+		let table = self.gen_table.get_or_init(|| self.build_gen_table());
+		let k = k.to_uint();
+		let mut res = JacobianPoint::identity();
+
+		// kG = sum(k_i * 2^(4i) * G), where k_i is the i-th 4-bit window of the scalar
+		for (i, row) in table.iter().enumerate() {
+			let digit = (0..GEN_WINDOW).fold(0, |acc, b| acc | (k.bit(i * GEN_WINDOW + b) as usize) << b);
+
+			// Read all the entries to not leak the digit through the memory access pattern
+			let (mut x, mut y) = row[0];
+			for (j, entry) in row.iter().enumerate().skip(1) {
+				x = C::Field::conditional_select(&x, &entry.0, digit == j + 1);
+				y = C::Field::conditional_select(&y, &entry.1, digit == j + 1);
+			}
+
+			// The sum is never doubling: res = k' * G, where k' < 2^(4i), and k' + digit * 2^(4i) <= k < n
+			let sum = res.add_affine_ct(&x, &y);
+			res = JacobianPoint::conditional_select(&res, &sum, digit != 0);
+		}
+
+		ECPoint::from_jacobian(&res)
 	}
-};
 
-fn clock_add(a: &Uint256, b: &Uint256, p: &Uint256) -> Uint256 {
-	let mut res = Uint512::from(*a) + Uint512::from(*b);
-	let p = Uint512::from(*p);
-	if res > p { res = res - p; }
-	Uint256::from(res)
-}
+	/// Computes `aP + bQ` in the variable time, should be used only with the public scalars.
+	///
+	/// Faster than two separate multiplications, because the doublings are shared (Strauss-Shamir trick).
+	pub fn double_mul(&self, a: &C::Scalar, p: &ECPoint<C>, b: &C::Scalar, q: &ECPoint<C>) -> ECPoint<C> {
+		self.multi_mul(&[(*a, *p), (*b, *q)])
+	}
+
+	/// Computes `sum(k_i * P_i)` in the variable time, should be used only with the public scalars.
+	///
+	/// Uses the interleaved wNAF method for the few points and the Pippenger algorithm for the many points.
+	/// If the curve has the endomorphism, every scalar is split into two halves of the half length.
+	pub fn multi_mul(&self, terms: &[(C::Scalar, ECPoint<C>)]) -> ECPoint<C> {
+		let mut affine = Vec::with_capacity(terms.len() * 2);
+		for (k, p) in terms.iter().filter(|&(_, p)| !p.infinity) {
+			match self.endomorphism {
+				Some(ref endo) => affine.extend_from_slice(&endo.split(k, &p.x, &p.y)),
+				None => affine.push((k.to_uint(), (p.x, p.y)))
+			}
+		}
+		let terms = affine;
 
-fn clock_sub(a: &Uint256, b: &Uint256, p: &Uint256) -> Uint256 {
-	if a < b {
-		*p - (*b - *a)
-	} else {
-		*a - *b
+		let res = if terms.len() < msm::PIPPENGER_THRESHOLD {
+			msm::strauss(&terms, &self.params.0)
+		} else {
+			msm::pippenger(&terms, &self.params.0)
+		};
+
+		ECPoint::from_jacobian(&res)
+	}
+
+	/// Returns `true` if `(x, y)` satisfies the curve equation `y^2 = x^3 + ax + b`.
+	fn contains(&self, x: &C::Field, y: &C::Field) -> bool {
+		let (a, b) = self.params;
+		y.square() == x.square() * *x + a * *x + b
+	}
+
+	/// Builds the table of the generation point multiples.
+	fn build_gen_table(&self) -> GenTable<C::Field> {
+		let a = self.params.0;
+		let rows = C::Scalar::NUM_BITS.div_ceil(GEN_WINDOW);
+		let mut points = Vec::with_capacity(rows << GEN_WINDOW);
+		let mut base = self.gen_point().to_jacobian();
+
+		for _ in 0..rows {
+			// base, 2 * base, ..., 15 * base
+			let mut p = base;
+			for _ in 1..1 << GEN_WINDOW {
+				points.push(p);
+				p = p.add(&base, &a);
+			}
+
+			// 16 * base
+			base = p;
+		}
+
+		let affine = JacobianPoint::batch_to_affine(&points);
+		affine.chunks((1 << GEN_WINDOW) - 1)
+			.map(|chunk| {
+				let mut row = [(C::Field::zero(), C::Field::zero()); (1 << GEN_WINDOW) - 1];
+				for (entry, p) in row.iter_mut().zip(chunk) {
+					*entry = p.expect("Multiples of the generation point shouldn't be at infinity.");
+				}
+				row
+			})
+			.collect()
 	}
 }
 
-fn clock_mul(a: &Uint256, b: &Uint256, p: &Uint256) -> Uint256 {
-	let mut res = Uint512::from(*a) * Uint512::from(*b);
-	let p = Uint512::from(*p);
+/// Secp256k1 curve marker.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp256k1;
+
+impl Curve for Secp256k1 {
+	type Field = FieldElement;
+	type Scalar = Scalar;
 
-	if res > p {
-		let div = res / p;
-		res = res - div * p;
+	fn curve() -> &'static EllipticCurve<Self> {
+		&SECP256K1
 	}
+}
+
+/// Secp256r1 (NIST P-256) curve marker.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp256r1;
+
+impl Curve for Secp256r1 {
+	type Field = Secp256r1FieldElement;
+	type Scalar = Secp256r1Scalar;
 
-	Uint256::from(res)
+	fn curve() -> &'static EllipticCurve<Self> {
+		&SECP256R1
+	}
 }
 
-fn clock_square(a: &Uint256, p: &Uint256) -> Uint256 {
-	clock_mul(a, a, p)
+/// Secp224k1 curve marker.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp224k1;
+
+impl Curve for Secp224k1 {
+	type Field = Secp224k1FieldElement;
+	type Scalar = Secp224k1Scalar;
+
+	fn curve() -> &'static EllipticCurve<Self> {
+		&SECP224K1
+	}
 }
 
-fn clock_div(a: &Uint256, b: &Uint256, p: &Uint256) -> Uint256 {
-	// Cast `p`
-	let p_i = Int512::from(Uint512::from(*p));
+/// Secp256k1.
+pub static SECP256K1: EllipticCurve<Secp256k1> = EllipticCurve {
+	params: (FieldElement::new(Uint256([0; 4])), FieldElement::new(Uint256([7, 0, 0, 0]))),
+	gen_point: (
+		FieldElement::new(Uint256([0x59f2815b16f81798, 0x029bfcdb2dce28d9, 0x55a06295ce870b07, 0x79be667ef9dcbbac])),
+		FieldElement::new(Uint256([0x9c47d08ffb10d4b8, 0xfd17b448a6855419, 0x5da4fbfc0e1108a8, 0x483ada7726a3c465]))
+	),
+	cofactor: 1,
+	gen_table: OnceLock::new(),
+	endomorphism: Some(Endomorphism {
+		beta: FieldElement::new(Uint256([0xc1396c28719501ee, 0x9cf0497512f58995, 0x6e64479eac3434e9, 0x7ae96a2b657c0710])),
+		lambda: Scalar::new(Uint256([0xdf02967c1b23bd72, 0x122e22ea20816678, 0xa5261c028812645a, 0x5363ad4cc05c30e0])),
+		g1: Uint256([0xe893209a45dbb031, 0x3daa8a1471e8ca7f, 0xe86c90e49284eb15, 0x3086d221a7d46bcd]),
+		g2: Uint256([0x1571b4ae8ac47f71, 0x221208ac9df506c6, 0x6f547fa90abfe4c4, 0xe4437ed6010e8828]),
+		minus_b1: Scalar::new(Uint256([0x6f547fa90abfe4c3, 0xe4437ed6010e8828, 0, 0])),
+		minus_b2: Scalar::new(Uint256([0xd765cda83db1562c, 0x8a280ac50774346d, 0xfffffffffffffffe, 0xffffffffffffffff]))
+	})
+};
+
+/// Secp256r1 (NIST P-256).
+pub static SECP256R1: EllipticCurve<Secp256r1> = EllipticCurve {
+	params: (
+		Secp256r1FieldElement::new(Uint256([0xfffffffffffffffc, 0x00000000ffffffff, 0x0000000000000000, 0xffffffff00000001])),
+		Secp256r1FieldElement::new(Uint256([0x3bce3c3e27d2604b, 0x651d06b0cc53b0f6, 0xb3ebbd55769886bc, 0x5ac635d8aa3a93e7]))
+	),
+	gen_point: (
+		Secp256r1FieldElement::new(Uint256([0xf4a13945d898c296, 0x77037d812deb33a0, 0xf8bce6e563a440f2, 0x6b17d1f2e12c4247])),
+		Secp256r1FieldElement::new(Uint256([0xcbb6406837bf51f5, 0x2bce33576b315ece, 0x8ee7eb4a7c0f9e16, 0x4fe342e2fe1a7f9b]))
+	),
+	cofactor: 1,
+	gen_table: OnceLock::new(),
+	endomorphism: None
+};
+
+/// Secp224k1.
+pub static SECP224K1: EllipticCurve<Secp224k1> = EllipticCurve {
+	params: (Secp224k1FieldElement::new(Uint256([0; 4])), Secp224k1FieldElement::new(Uint256([5, 0, 0, 0]))),
+	gen_point: (
+		Secp224k1FieldElement::new(Uint256([0x0f7e650eb6b7a45c, 0x69a467e9e47075a9, 0x4df099df30fc28a1, 0x00000000a1455b33])),
+		Secp224k1FieldElement::new(Uint256([0xe2ca4bdb556d61a5, 0xf7e319f7c0b0bd59, 0x7fba344282cafbd6, 0x000000007e089fed]))
+	),
+	cofactor: 1,
+	gen_table: OnceLock::new(),
+	endomorphism: None
+};
 
-	// Compute multiplicative inverse of the b (b^(-1))
+/// Error of the curve parameters validation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CurveError {
+	/// Modulo of the coordinate field isn't a prime number greater than 3.
+	ModuloNotPrime,
 
-	let (mut t, mut nt, mut r, mut nr) = (Int512::zero(), Int512::from(1), p_i, Int512::from(Uint512::from(*b)));
+	/// Order of the generation point isn't a prime number.
+	OrderNotPrime,
 
-	while nr != Int512::zero() {
-		let q = r / nr;
+	/// Curve is singular: `4a^3 + 27b^2 = 0`.
+	SingularCurve,
 
-		let bt = t;
-		t = nt;
-		nt = bt - q * nt;
+	/// Order of the generation point isn't greater than `4 * sqrt(p)`.
+	OrderTooSmall,
 
-		let br = r;
-		r = nr;
-		nr = br - q * nr;
-	}
+	/// Cofactor doesn't give the number of the curve points within the Hasse bound.
+	InvalidCofactor,
 
-	if r > Int512::from(1) {
-		panic!("Cannot invert 'a'.");
-	}
+	/// Generation point doesn't satisfy the curve equation.
+	GeneratorNotOnCurve,
 
-	if t < Int512::zero() {
-		t = t + p_i;
+	/// Generation point doesn't have the order of the scalar field.
+	InvalidGeneratorOrder,
+
+	/// Parameters of the curve are already registered.
+	AlreadyRegistered
+}
+
+impl fmt::Display for CurveError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			CurveError::ModuloNotPrime => write!(f, "Curve field modulo is not prime."),
+			CurveError::OrderNotPrime => write!(f, "Curve order is not prime."),
+			CurveError::SingularCurve => write!(f, "Curve is singular."),
+			CurveError::OrderTooSmall => write!(f, "Curve order is too small for the field."),
+			CurveError::InvalidCofactor => write!(f, "Curve cofactor is invalid."),
+			CurveError::GeneratorNotOnCurve => write!(f, "Generation point is not on the curve."),
+			CurveError::InvalidGeneratorOrder => write!(f, "Generation point has the wrong order."),
+			CurveError::AlreadyRegistered => write!(f, "Curve is already registered.")
+		}
 	}
+}
+
+impl Error for CurveError { }
+
+/// Error of the point validation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PointError {
+	/// Coordinate isn't less than the field modulo.
+	CoordinateOutOfRange,
+
+	/// Point doesn't satisfy the curve equation.
+	NotOnCurve,
+
+	/// Point doesn't belong to the subgroup generated by the generation point.
+	NotInSubgroup,
+
+	/// Point is the point at infinity, which isn't allowed in this context.
+	PointAtInfinity,
+
+	/// Encoded point has the wrong length or prefix.
+	InvalidEncoding,
 
-	// Multiply: a * b^(-1) (mod p)
-	clock_mul(a, &Uint256::from(Uint512::from(t)), p)
+	/// Encoded point has the hybrid format, which isn't allowed.
+	HybridEncoding
 }
 
-/// Point on the elliptic curve.
-#[derive(Clone, Copy)]
-pub struct ECPoint<'a> {
+impl fmt::Display for PointError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			PointError::CoordinateOutOfRange => write!(f, "Point coordinate is out of the field range."),
+			PointError::NotOnCurve => write!(f, "Point is not on the curve."),
+			PointError::NotInSubgroup => write!(f, "Point is not in the subgroup of the generation point."),
+			PointError::PointAtInfinity => write!(f, "Point is the point at infinity."),
+			PointError::InvalidEncoding => write!(f, "Point encoding is invalid."),
+			PointError::HybridEncoding => write!(f, "Hybrid point encoding is not allowed.")
+		}
+	}
+}
+
+impl Error for PointError { }
+
+/// Point on the elliptic curve `C`.
+///
+/// The curve is defined by the type, so the point doesn't store the curve reference.
+pub struct ECPoint<C: Curve = Secp256k1> {
 	/// The `x` coordinate of the point.
-	x: Uint256,
+	x: C::Field,
 
 	/// The `y` coordinate of the point.
-	y: Uint256,
+	y: C::Field,
+
+	/// Is the point at infinity.
+	infinity: bool,
 
 	/// The curve that associated to the point.
-	curve: &'a EllipticCurve<'a>
+	curve: PhantomData<C>
 }
 
-impl<'a> ECPoint<'a> {
-	pub fn new(x: Uint256, y: Uint256, curve: &'a EllipticCurve) -> Self {
-		ECPoint { x, y, curve }
+impl<C: Curve> ECPoint<C> {
+	/// Creates point on the curve.
+	///
+	/// Coordinates aren't validated, use `EllipticCurve::try_create_point` for the untrusted input.
+	pub fn new(x: C::Field, y: C::Field) -> Self {
+		ECPoint { x, y, infinity: false, curve: PhantomData }
 	}
 
-	/// Returns `x` component of the elliptic curve point.
-	pub fn x(&self) -> &Uint256 { &self.x }
+	/// Creates the point at infinity on the curve.
+	pub fn identity() -> Self {
+		ECPoint { x: C::Field::zero(), y: C::Field::zero(), infinity: true, curve: PhantomData }
+	}
+
+	/// Returns `x` component of the elliptic curve point, it is zero for the point at infinity.
+	pub fn x(&self) -> &C::Field { &self.x }
+
+	/// Returns `y` component of the elliptic curve point, it is zero for the point at infinity.
+	pub fn y(&self) -> &C::Field { &self.y }
+
+	/// Returns `true` if the point is the point at infinity.
+	pub fn is_identity(&self) -> bool { self.infinity }
+
+	/// Converts the point into the Jacobian coordinates.
+	pub(crate) fn to_jacobian(self) -> JacobianPoint<C::Field> {
+		if self.infinity {
+			JacobianPoint::identity()
+		} else {
+			JacobianPoint::from_affine(self.x, self.y)
+		}
+	}
+
+	/// Converts the point from the Jacobian coordinates.
+	pub(crate) fn from_jacobian(point: &JacobianPoint<C::Field>) -> Self {
+		match point.to_affine() {
+			Some((x, y)) => ECPoint::new(x, y),
+			None => ECPoint::identity()
+		}
+	}
+
+	/// Returns `true` if the point satisfies the curve equation `y^2 = x^3 + ax + b`.
+	pub fn is_on_curve(&self) -> bool {
+		self.infinity || C::curve().contains(&self.x, &self.y)
+	}
 
-	/// Returns `y` component of the elliptic curve point.
-	pub fn y(&self) -> &Uint256 { &self.y }
+	/// Returns `true` if the point belongs to the subgroup generated by the generation point (`nP = O`).
+	pub fn is_in_subgroup(&self) -> bool {
+		// nP = O <=> (n - 1)P = -P
+		self.mul_vartime(&-C::Scalar::one()) == -*self
+	}
+
+	/// Multiplies the point by the scalar in the variable time.
+	///
+	/// Faster than the multiplication operator, but leaks the scalar through the timing,
+	/// so it should be used only with the public scalars (e.g. in the signature verification).
+	pub fn mul_vartime(&self, k: &C::Scalar) -> Self {
+		C::curve().multi_mul(&[(*k, *self)])
+	}
 
 	/// Returns associated elliptic `curve`.
-	pub fn curve(&self) -> &EllipticCurve { &self.curve }
+	pub fn curve(&self) -> &'static EllipticCurve<C> { C::curve() }
 
 	/// Doubles the point.
 	pub fn double(&self) -> Self {
-		let mut res = ECPoint { x: Uint256::zero(), y: Uint256::zero(), curve: self.curve };
-		let p = self.curve.modulo();
-		let a = self.curve.params.0;
-		let (x, y) = (self.x, self.y);
-
-		// Calc `X`
-		let xp2 = clock_square(&x, p);						// x^2
-		let xp2m3 = clock_mul(&xp2, &Uint256::from(3), p);	// 3x^2
-		let xaa = clock_add(&xp2m3, &a, p);					// 3x^2 + a
-		let ym2 = clock_mul(&y, &Uint256::from(2), p);		// 2y
-		let div = clock_div(&xaa, &ym2, p);					// (3x^2 + a) / 2y
-		let divp2 = clock_square(&div, p);					// ((3x^2 + a) / 2y)^2
-		let xm2 = clock_mul(&x, &Uint256::from(2), p);		// 2x
-		res.x = clock_sub(&divp2, &xm2, p);					// ((3x^2 + a) / 2y)^2 - 2x
-
-		// Calc `Y`
-		let x1sx3 = clock_sub(&x, &res.x, p);				// x_1 - x_3
-		let dmx1s3 = clock_mul(&div, &x1sx3, p);			// ((3x^2 + a) / 2y) * (x_1 - x_3)
-		res.y = clock_sub(&dmx1s3, &y, p);					// ((3x^2 + a) / 2y) * (x_1 - x_3) - y
-
-		res
+		let res = self.to_jacobian().double(C::curve().a());
+		ECPoint::from_jacobian(&res)
 	}
 }
 
-impl<'a> fmt::Debug for ECPoint<'a> {
+impl<C: Curve> Clone for ECPoint<C> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<C: Curve> Copy for ECPoint<C> { }
+
+impl<C: Curve> fmt::Debug for ECPoint<C> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-		write!(f, "({:?}, {:?})", self.x, self.y)?;
+		if self.infinity {
+			write!(f, "Infinity")?;
+		} else {
+			write!(f, "({:?}, {:?})", self.x, self.y)?;
+		}
+
 		Ok(())
 	}
 }
 
-impl<'a> PartialEq for ECPoint<'a> {
-	fn eq(&self, other: &ECPoint) -> bool {
-		self.x == other.x && self.y == other.y && ::std::ptr::eq(self.curve, other.curve)
+/// The points of the different curves have the different types, so they can't be compared.
+impl<C: Curve> PartialEq for ECPoint<C> {
+	fn eq(&self, other: &Self) -> bool {
+		if self.infinity || other.infinity {
+			self.infinity == other.infinity
+		} else {
+			self.x == other.x && self.y == other.y
+		}
 	}
 }
 
-impl<'a> Add for ECPoint<'a> {
+impl<C: Curve> Eq for ECPoint<C> { }
+
+impl<C: Curve> Add for ECPoint<C> {
 	type Output = Self;
 
 	fn add(self, other: Self) -> Self {
-		let mut res = ECPoint { x: Uint256::zero(), y: Uint256::zero(), curve: self.curve };
-		let p = self.curve.modulo();
-		let (x1, y1) = (self.x, self.y);
-		let (x2, y2) = (other.x, other.y);
-
-		// Calc `X`
-		let y2sy1 = clock_sub(&y2, &y1, p);			// y_2 - y_1
-		let x2sx1 = clock_sub(&x2, &x1, p); 		// x_2 - x_1
-		let div = clock_div(&y2sy1, &x2sx1, p);		// (y_2 - y_1) / (x_2 - x_1)
-		let dp2 = clock_square(&div, p);			// ((y_2 - y_1) / (x_2 - x_1))^2
-		let dp2sx1 = clock_sub(&dp2, &x1, p);		// ((y_2 - y_1) / (x_2 - x_1))^2 - x_1
-		res.x = clock_sub(&dp2sx1, &x2, p);			// ((y_2 - y_1) / (x_2 - x_1))^2 - x_1 - x_2
-
-		// Calc `Y`
-		let x1sx3 = clock_sub(&x1, &res.x, p);		// x_1 - x_3
-		let dmx1sx3 = clock_mul(&div, &x1sx3, p);	// (y_2 - y_1) / (x_2 - x_1) * (x_1 - x_3)
-		res.y = clock_sub(&dmx1sx3, &y1, p);		// (y_2 - y_1) / (x_2 - x_1) * (x_1 - x_3) - y_1
-
-		res
+		let res = self.to_jacobian().add(&other.to_jacobian(), C::curve().a());
+		ECPoint::from_jacobian(&res)
 	}
 }
 
-impl<'a> Mul<Uint256> for ECPoint<'a> {
+impl<C: Curve> Neg for ECPoint<C> {
 	type Output = Self;
 
-	fn mul(self, other: Uint256) -> Self {
-		if other == Uint256::zero() {
-			panic!("Cannot multiply EC point by zero.");
-		} else if other == Uint256::one() {
+	fn neg(self) -> Self {
+		if self.infinity {
 			self
-		} else if other == Uint256::from(2) {
-			self.double()
-		} else if other.is_odd() {
-			self * (other - Uint256::from(1)) + self
-		} else { // even
-			(self * (other / Uint256::from(2))).double()
+		} else {
+			ECPoint::new(self.x, -self.y)
 		}
 	}
 }
 
-#[test]
-fn test_clock_add() {
-	let p = SECP256K1.modulo;
-
-	// 0x ffffffffffffffff ffffffffffffffff ffffffffffffffff fffffffeaffffc2f
-	// +
-	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a89c42b584
-	// =
-	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a84c42b584
-	// mod p
-
-	assert_eq!(
-		clock_add(&Uint256::from_raw([0xfffffffeaffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]),
-				  &Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]), &p),
-		Uint256::from_raw([0xbbf030a84c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f])
-	);
-}
+impl<C: Curve> Sub for ECPoint<C> {
+	type Output = Self;
 
-#[test]
-fn test_clock_sub() {
-	let p = SECP256K1.modulo;
-
-	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a89c42b584
-	// -
-	// 0x ffffffffffffffff ffffffffffffffff ffffffffffffffff fffffffeaffffc2f
-	// =
-	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a8ec42b584
-	// mod p
-
-	assert_eq!(
-		clock_sub(&Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]),
-				  &Uint256::from_raw([0xfffffffeaffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]), &p),
-		Uint256::from_raw([0xbbf030a8ec42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f])
-	);
+	fn sub(self, other: Self) -> Self {
+		self + -other
+	}
 }
 
-#[test]
-fn test_clock_mul() {
-	let p = SECP256K1.modulo;
-
-	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a89c42b584
-	// -
-	// 0x ffffffffffffffff ffffffffffffffff ffffffffffffffff fffffffeaffffc2f
-	// =
-	// 0x e412c74d14b788ee 2df139179c711a8c 1fed07ff8544f0cb 483c8c294b62698d
-	// mod p
-
-	assert_eq!(
-		clock_mul(&Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]),
-				  &Uint256::from_raw([0xfffffffeaffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]), &p),
-		Uint256::from_raw([0x483c8c294b62698d, 0x1fed07ff8544f0cb, 0x2df139179c711a8c, 0xe412c74d14b788ee])
-	);
-}
+impl<C: Curve> Mul<C::Scalar> for ECPoint<C> {
+	type Output = Self;
 
-#[test]
-fn test_clock_div() {
-	let p = SECP256K1.modulo;
+	/// Multiplies the point by the scalar in the constant time (Montgomery ladder).
+	fn mul(self, other: C::Scalar) -> Self {
+		let a = *C::curve().a();
+		let k = other.to_uint();
+		let (mut r0, mut r1) = (JacobianPoint::identity(), self.to_jacobian());
 
-	let div = clock_div(
-		&Uint256::from_raw([0xaa7f067e28fef8ac, 0xaa2f64be71462131, 0x42fd096d2f1f7fd9, 0x8ff2b776aaf6d919]),
-		&Uint256::from_raw([0x388fa11ff621a970, 0xfa2f68914d0aa833, 0xbb49f7f81c221151, 0x9075b4ee4d4788ca]),
-		&p
-	);
+		// Invariant: R1 - R0 = P, the number of iterations doesn't depend on the scalar
+		for i in (0..C::Scalar::NUM_BITS).rev() {
+			let bit = k.bit(i);
 
-	assert_eq!(div, Uint256::from_raw([0x36d3aebbeddcd1b1, 0xf58857c2f631ee69, 0x3eb9d1235992ac63, 0xcb35b28428101a30]));
+			JacobianPoint::conditional_swap(&mut r0, &mut r1, bit);
+			r1 = r0.add_ct(&r1, &a);
+			r0 = r0.double(&a);
+			JacobianPoint::conditional_swap(&mut r0, &mut r1, bit);
+		}
+
+		ECPoint::from_jacobian(&r0)
+	}
 }
\ No newline at end of file
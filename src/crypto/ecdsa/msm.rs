@@ -0,0 +1,175 @@
+use utils::bignum::Uint256;
+use crypto::field::PrimeField;
+use super::jacobian::JacobianPoint;
+
+/// Width of the wNAF window in the Strauss algorithm.
+const WNAF_WINDOW: usize = 5;
+
+/// Minimal number of points to use the Pippenger algorithm.
+pub(crate) const PIPPENGER_THRESHOLD: usize = 64;
+
+/// Computes `sum(k_i * P_i)` by the interleaved wNAF method (Strauss-Shamir trick) in the variable time.
+///
+/// Points are given in the affine coordinates, the points at infinity should be skipped by the caller.
+pub(crate) fn strauss<F: PrimeField>(terms: &[(Uint256, (F, F))], a: &F) -> JacobianPoint<F> {
+	let table_len = 1 << (WNAF_WINDOW - 2);
+
+	// Odd multiples P, 3P, ..., 15P of every point, converted into the affine coordinates at once
+	let mut multiples = Vec::with_capacity(terms.len() * table_len);
+	for &(_, (x, y)) in terms {
+		let p = JacobianPoint::from_affine(x, y);
+		let p2 = p.double(a);
+		let mut q = p;
+
+		for _ in 0..table_len {
+			multiples.push(q);
+			q = q.add(&p2, a);
+		}
+	}
+
+	let multiples = JacobianPoint::batch_to_affine(&multiples);
+	let digits: Vec<Vec<i32>> = terms.iter().map(|(k, _)| wnaf(k, WNAF_WINDOW)).collect();
+	let len = digits.iter().map(|d| d.len()).max().unwrap_or(0);
+	let mut res = JacobianPoint::identity();
+
+	// All the points share the doublings
+	for i in (0..len).rev() {
+		res = res.double(a);
+
+		for (j, d) in digits.iter().enumerate() {
+			let digit = d.get(i).cloned().unwrap_or(0);
+			if digit == 0 {
+				continue;
+			}
+
+			// The multiple is at infinity only if the point has the small order
+			if let Some((x, y)) = multiples[j * table_len + (digit.unsigned_abs() as usize - 1) / 2] {
+				res = if digit > 0 { res.add_affine(&x, &y, a) } else { res.add_affine(&x, &-y, a) };
+			}
+		}
+	}
+
+	res
+}
+
+/// Computes `sum(k_i * P_i)` by the Pippenger (bucket) algorithm in the variable time.
+///
+/// Points are given in the affine coordinates, the points at infinity should be skipped by the caller.
+pub(crate) fn pippenger<F: PrimeField>(terms: &[(Uint256, (F, F))], a: &F) -> JacobianPoint<F> {
+	// The window grows as the logarithm of the number of points
+	let log = (usize::BITS - terms.len().leading_zeros()) as usize;
+	let c = if log > 5 { log - 2 } else { 3 };
+	let len = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+	let mut res = JacobianPoint::identity();
+
+	for w in (0..len.div_ceil(c)).rev() {
+		for _ in 0..c {
+			res = res.double(a);
+		}
+
+		// buckets[d - 1] is the sum of the points which scalars have the digit `d` in this window
+		let mut buckets = vec![JacobianPoint::identity(); (1 << c) - 1];
+		for &(ref k, (x, y)) in terms {
+			let digit = window(k, w * c, c);
+			if digit != 0 {
+				buckets[digit - 1] = buckets[digit - 1].add_affine(&x, &y, a);
+			}
+		}
+
+		// sum(d * B_d) = B_max + (B_max + B_(max - 1)) + ... + (B_max + ... + B_1)
+		let (mut sum, mut acc) = (JacobianPoint::identity(), JacobianPoint::identity());
+		for bucket in buckets.iter().rev() {
+			sum = sum.add(bucket, a);
+			acc = acc.add(&sum, a);
+		}
+
+		res = res.add(&acc, a);
+	}
+
+	res
+}
+
+/// Returns `width` bits of `k` starting from the bit `start`.
+fn window(k: &Uint256, start: usize, width: usize) -> usize {
+	(0..width).fold(0, |acc, i| acc | (k.bit(start + i) as usize) << i)
+}
+
+/// Returns the width-`w` non-adjacent form of `k`, starting from the least significant digit.
+///
+/// Non-zero digits are odd, less than `2^(w - 1)` by the absolute value, and any `w`
+/// consecutive digits contain at most one non-zero digit.
+fn wnaf(k: &Uint256, w: usize) -> Vec<i32> {
+	// The carry may produce the digit above the most significant bit
+	let len = k.bits() + w;
+	let mut res = vec![0; len];
+	let (mut i, mut carry) = (0, 0);
+
+	while i < len {
+		if k.bit(i) as i32 == carry {
+			i += 1;
+			continue;
+		}
+
+		// The digit is odd: the bit `i` plus the carry is 1
+		let mut digit = window(k, i, w) as i32 + carry;
+		carry = (digit >> (w - 1)) & 1;
+		digit -= carry << w;
+
+		res[i] = digit;
+		i += w;
+	}
+
+	while res.last() == Some(&0) {
+		res.pop();
+	}
+
+	res
+}
+
+#[cfg(test)]
+use super::{SECP256K1, ECPoint, FieldElement, Scalar};
+
+#[cfg(test)]
+fn terms(count: u64) -> Vec<(Uint256, (FieldElement, FieldElement))> {
+	let g = SECP256K1.gen_point();
+	(1..=count)
+		.map(|i| {
+			let p = g * Scalar::from(i * 7919);
+			(Uint256::from_raw([i.wrapping_mul(0x9e3779b97f4a7c15), i, 0, i << 32]), (*p.x(), *p.y()))
+		})
+		.collect()
+}
+
+#[test]
+fn test_wnaf() {
+	for &k in [0u64, 1, 15, 16, 31, 63, 0x3e0, 0x5555_5555_5555_5555, 0xffff_ffff_ffff_ffff, 0x8ee3_c261_3bf9_9522].iter() {
+		let digits = wnaf(&Uint256::from(k), WNAF_WINDOW);
+		let sum = digits.iter().rev().fold(0i128, |acc, &d| acc * 2 + d as i128);
+		assert_eq!(sum, k as i128);
+
+		for (i, &d) in digits.iter().enumerate() {
+			if d != 0 {
+				assert!(d % 2 != 0 && d.abs() < 1 << (WNAF_WINDOW - 1));
+				assert!(digits[i + 1..].iter().take(WNAF_WINDOW - 1).all(|&d| d == 0));
+			}
+		}
+	}
+}
+
+#[test]
+fn test_strauss_pippenger() {
+	let a = *SECP256K1.a();
+
+	for count in [1, 2, 5, 20].iter() {
+		let terms = terms(*count);
+		let expected = terms.iter().fold(SECP256K1.identity(), |acc, &(ref k, (x, y))| {
+			acc + SECP256K1.create_point(x, y).mul_vartime(&Scalar::reduce(k))
+		});
+
+		assert_eq!(ECPoint::from_jacobian(&strauss(&terms, &a)), expected);
+		assert_eq!(ECPoint::from_jacobian(&pippenger(&terms, &a)), expected);
+	}
+
+	assert!(strauss::<FieldElement>(&[], &a).is_identity());
+	assert!(pippenger::<FieldElement>(&[], &a).is_identity());
+}
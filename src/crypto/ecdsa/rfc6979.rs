@@ -0,0 +1,84 @@
+use crypto::field::PrimeField;
+use crypto::hash::{HmacSha256, hmac_sha256};
+use super::signature::bits_to_uint;
+
+/// Deterministic generator of the ECDSA nonces (RFC 6979), HMAC-DRBG over SHA-256.
+pub(crate) struct NonceGenerator {
+	/// The `K` key of the HMAC-DRBG.
+	k: [u8; 32],
+
+	/// The `V` value of the HMAC-DRBG.
+	v: [u8; 32],
+
+	/// Should the state be updated before the next nonce, i.e. some nonce is already generated.
+	retry: bool
+}
+
+impl NonceGenerator {
+	/// Creates generator for the private key `d` and the message hash.
+	///
+	/// The additional data `extra` is appended to the seed (RFC 6979, section 3.6), it's empty for the plain RFC 6979.
+	pub fn new<S: PrimeField>(d: &S, msg_hash: &[u8; 32], extra: &[u8]) -> Self {
+		// int2octets(x) || bits2octets(h1) || extra
+		let rlen = S::NUM_BITS.div_ceil(8);
+		let h = S::reduce(&bits_to_uint::<S>(msg_hash));
+		let mut seed = Vec::with_capacity(2 * rlen + extra.len());
+		seed.extend_from_slice(&d.to_bytes()[32 - rlen..]);
+		seed.extend_from_slice(&h.to_bytes()[32 - rlen..]);
+		seed.extend_from_slice(extra);
+
+		let mut gen = NonceGenerator { k: [0; 32], v: [1; 32], retry: false };
+		gen.reseed(&seed);
+		gen
+	}
+
+	/// Returns the next nonce in the `[1; q)` range.
+	pub fn next<S: PrimeField>(&mut self) -> S {
+		loop {
+			if self.retry {
+				self.reseed(&[]);
+			}
+			self.retry = true;
+
+			// T = V, its length is enough for the orders up to 256 bits
+			self.v = hmac_sha256(&self.k, &self.v);
+
+			if let Some(k) = S::from_uint(&bits_to_uint::<S>(&self.v)) {
+				if !k.is_zero() {
+					return k;
+				}
+			}
+		}
+	}
+
+	/// Updates the state: `K = HMAC_K(V || 0x00 || seed)`, `V = HMAC_K(V)` and the same with `0x01` if the seed isn't empty.
+	fn reseed(&mut self, seed: &[u8]) {
+		for &byte in [0x00u8, 0x01].iter().take(if seed.is_empty() { 1 } else { 2 }) {
+			let mut hmac = HmacSha256::new(&self.k);
+			hmac.update(&self.v);
+			hmac.update(&[byte]);
+			hmac.update(seed);
+			self.k = hmac.finalize();
+			self.v = hmac_sha256(&self.k, &self.v);
+		}
+	}
+}
+
+#[cfg(test)]
+use utils::bignum::Uint256;
+#[cfg(test)]
+use super::Secp256r1Scalar;
+#[cfg(test)]
+use crypto::hash::sha256;
+
+#[test]
+fn test_nonce_generator() {
+	// RFC 6979, A.2.5: P-256 with SHA-256
+	let d = Secp256r1Scalar::new(Uint256::from_raw([0x7b8a622b120f6721, 0x4e50c3db36e89b12, 0x6b5c215767b1d693, 0xc9afa9d845ba7516]));
+
+	let k: Secp256r1Scalar = NonceGenerator::new(&d, &sha256(b"sample"), &[]).next();
+	assert_eq!(k.to_uint(), Uint256::from_raw([0x4d6129493d8aad60, 0x3b17aa873382b0f2, 0x086538398355dd4c, 0xa6e3c57dd01abe90]));
+
+	let k: Secp256r1Scalar = NonceGenerator::new(&d, &sha256(b"test"), &[]).next();
+	assert_eq!(k.to_uint(), Uint256::from_raw([0xc2537acaee0008e0, 0x0192c4c92677336e, 0xe040871a1c7ec350, 0xd16b6ae827f17175]));
+}
@@ -0,0 +1,115 @@
+use crypto::field::PrimeField;
+use super::{Curve, EllipticCurve, ECPoint, PointError, PublicKey};
+
+/// Format of the point encoding (SEC 1, section 2.3.3).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PointFormat {
+	/// `0x04 || x || y`.
+	Uncompressed,
+
+	/// `0x02 || x` for the even `y` and `0x03 || x` for the odd `y`.
+	Compressed,
+
+	/// `0x06 || x || y` for the even `y` and `0x07 || x || y` for the odd `y`.
+	///
+	/// Legacy format, which is rejected by the decoding by default.
+	Hybrid
+}
+
+impl<C: Curve> ECPoint<C> {
+	/// Encodes the point in the SEC 1 format, the point at infinity is encoded as the single zero byte.
+	pub fn encode(&self, format: PointFormat) -> Vec<u8> {
+		if self.infinity {
+			return vec![0];
+		}
+
+		let len = field_len::<C::Field>();
+		let odd = self.y.to_uint().is_odd() as u8;
+		let mut res = Vec::with_capacity(1 + 2 * len);
+
+		res.push(match format {
+			PointFormat::Uncompressed => 0x04,
+			PointFormat::Compressed => 0x02 | odd,
+			PointFormat::Hybrid => 0x06 | odd
+		});
+		res.extend_from_slice(&self.x.to_bytes()[32 - len..]);
+
+		if format != PointFormat::Compressed {
+			res.extend_from_slice(&self.y.to_bytes()[32 - len..]);
+		}
+
+		res
+	}
+}
+
+impl<C: Curve> EllipticCurve<C> {
+	/// Decodes the point from the SEC 1 format, checking that it's a valid point of the curve.
+	///
+	/// The `y` coordinate of the compressed point is restored by the modular square root.
+	/// The hybrid format is accepted only if `allow_hybrid` is set.
+	pub fn decode_point(&self, bytes: &[u8], allow_hybrid: bool) -> Result<ECPoint<C>, PointError> {
+		let len = field_len::<C::Field>();
+
+		match (bytes.first(), bytes.len()) {
+			(Some(0x00), 1) => Ok(self.identity()),
+			(Some(&prefix @ 0x02..=0x03), l) if l == 1 + len => {
+				self.lift_x(&coordinate::<C::Field>(&bytes[1..])?, prefix == 0x03)
+			},
+			(Some(&prefix @ (0x04 | 0x06 | 0x07)), l) if l == 1 + 2 * len => {
+				let x = coordinate::<C::Field>(&bytes[1..1 + len])?;
+				let y = coordinate::<C::Field>(&bytes[1 + len..])?;
+
+				if prefix != 0x04 {
+					if !allow_hybrid {
+						return Err(PointError::HybridEncoding);
+					}
+
+					// The prefix should match the parity of `y`
+					if y.to_uint().is_odd() != (prefix == 0x07) {
+						return Err(PointError::InvalidEncoding);
+					}
+				}
+
+				self.try_create_point(x.to_uint(), y.to_uint())
+			},
+			_ => Err(PointError::InvalidEncoding)
+		}
+	}
+
+	/// Returns the point with the `x` coordinate and the `y` coordinate of the given parity,
+	/// checking that it's a valid point of the curve.
+	pub fn lift_x(&self, x: &C::Field, odd: bool) -> Result<ECPoint<C>, PointError> {
+		// y^2 = x^3 + ax + b
+		let (a, b) = self.params;
+		let y = (x.square() * *x + a * *x + b).sqrt().ok_or(PointError::NotOnCurve)?;
+		let y = if y.to_uint().is_odd() == odd { y } else { -y };
+
+		// The subgroup is still checked if the curve has the cofactor
+		self.try_create_point(x.to_uint(), y.to_uint())
+	}
+}
+
+impl<C: Curve> PublicKey<C> {
+	/// Encodes the key in the SEC 1 format.
+	pub fn encode(&self, format: PointFormat) -> Vec<u8> {
+		self.point().encode(format)
+	}
+
+	/// Decodes the key from the SEC 1 compressed or uncompressed format.
+	pub fn decode(bytes: &[u8]) -> Result<Self, PointError> {
+		PublicKey::new(C::curve().decode_point(bytes, false)?)
+	}
+}
+
+/// Returns the length of the field element in bytes.
+fn field_len<F: PrimeField>() -> usize {
+	F::NUM_BITS.div_ceil(8)
+}
+
+/// Decodes the big-endian field element, checking that it's less than the modulo.
+fn coordinate<F: PrimeField>(bytes: &[u8]) -> Result<F, PointError> {
+	let mut buf = [0u8; 32];
+	buf[32 - bytes.len()..].copy_from_slice(bytes);
+
+	F::from_bytes(&buf).ok_or(PointError::CoordinateOutOfRange)
+}
@@ -0,0 +1,471 @@
+use utils::bignum::{Uint256, Zero};
+use crypto::field::PrimeField;
+use super::{Curve, Secp256k1, EllipticCurve, ECPoint, PointError, Scalar};
+use super::rfc6979::NonceGenerator;
+use std::error::Error;
+use std::fmt;
+
+/// ECDSA private key: the non-zero scalar `d`.
+pub struct PrivateKey<C: Curve = Secp256k1> {
+	/// The secret scalar.
+	d: C::Scalar
+}
+
+impl<C: Curve> PrivateKey<C> {
+	/// Creates private key from the scalar, returns `None` if the scalar is zero.
+	pub fn new(d: C::Scalar) -> Option<Self> {
+		if d.is_zero() {
+			None
+		} else {
+			Some(PrivateKey { d })
+		}
+	}
+
+	/// Returns the secret scalar of the key.
+	pub fn scalar(&self) -> &C::Scalar { &self.d }
+
+	/// Returns associated elliptic `curve`.
+	pub fn curve(&self) -> &'static EllipticCurve<C> { C::curve() }
+
+	/// Returns the public key `Q = dG`.
+	pub fn public_key(&self) -> PublicKey<C> {
+		PublicKey { point: C::curve().mul_generator(&self.d) }
+	}
+
+	/// Signs the message hash with the deterministic nonce (RFC 6979 with SHA-256).
+	pub fn sign(&self, msg_hash: &[u8; 32]) -> Signature<C::Scalar> {
+		self.sign_deterministic(msg_hash, &[]).sig
+	}
+
+	/// Signs the message hash with the deterministic nonce, the public key can be recovered from the result.
+	pub fn sign_recoverable(&self, msg_hash: &[u8; 32]) -> RecoverableSignature<C::Scalar> {
+		self.sign_deterministic(msg_hash, &[])
+	}
+
+	/// Signs the message hash with the deterministic nonce, which also depends on the extra entropy
+	/// (the additional data of RFC 6979, section 3.6).
+	///
+	/// The signature is still valid if the entropy is bad, but it differs from `sign` result
+	/// and protects from the fault attacks if the entropy is random.
+	pub fn sign_with_entropy(&self, msg_hash: &[u8; 32], entropy: &[u8; 32]) -> Signature<C::Scalar> {
+		self.sign_deterministic(msg_hash, entropy).sig
+	}
+
+	/// Signs the message hash with the nonce `k`.
+	///
+	/// The nonce should be secret and unique for every signature, reusing it reveals the private key.
+	/// Returns `None` if the nonce is zero or produces the zero signature component,
+	/// the message should be signed with another nonce in this case.
+	pub fn sign_with_nonce(&self, msg_hash: &[u8; 32], k: &C::Scalar) -> Option<Signature<C::Scalar>> {
+		self.sign_recoverable_with_nonce(msg_hash, k).map(|sig| sig.sig)
+	}
+
+	/// Signs the message hash with the nonce `k`, the public key can be recovered from the result.
+	///
+	/// See `sign_with_nonce` for the requirements to the nonce.
+	pub fn sign_recoverable_with_nonce(&self, msg_hash: &[u8; 32], k: &C::Scalar) -> Option<RecoverableSignature<C::Scalar>> {
+		if k.is_zero() {
+			return None;
+		}
+
+		// r = x(kG) (mod n)
+		let point = C::curve().mul_generator(k);
+		let x = point.x.to_uint();
+		let r = C::Scalar::reduce(&x);
+
+		// s = k^(-1) * (z + r * d) (mod n)
+		let s = (hash_to_scalar::<C::Scalar>(msg_hash) + r * self.d) * k.invert()?;
+
+		// The point kG is restored from `r` by the parity of `y` and the fact that `x` isn't less than `n`
+		let recovery_id = point.y.to_uint().is_odd() as u8 | ((x >= C::Scalar::modulo()) as u8) << 1;
+
+		Signature::new(r, s).map(|sig| RecoverableSignature { sig, recovery_id })
+	}
+
+	/// Signs the message hash with the RFC 6979 nonces, using the next nonce while the signature is invalid.
+	fn sign_deterministic(&self, msg_hash: &[u8; 32], extra: &[u8]) -> RecoverableSignature<C::Scalar> {
+		let mut gen = NonceGenerator::new(&self.d, msg_hash, extra);
+
+		loop {
+			if let Some(sig) = self.sign_recoverable_with_nonce(msg_hash, &gen.next()) {
+				return sig;
+			}
+		}
+	}
+}
+
+impl<C: Curve> Clone for PrivateKey<C> {
+	fn clone(&self) -> Self {
+		PrivateKey { d: self.d }
+	}
+}
+
+impl<C: Curve> fmt::Debug for PrivateKey<C> {
+	/// Doesn't output the secret scalar.
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		write!(f, "PrivateKey(..)")
+	}
+}
+
+/// ECDSA public key: the point `Q = dG`.
+pub struct PublicKey<C: Curve = Secp256k1> {
+	/// The point of the key.
+	point: ECPoint<C>
+}
+
+impl<C: Curve> PublicKey<C> {
+	/// Creates public key from the point, checking that it's a valid point of the subgroup
+	/// generated by the generation point and it isn't the point at infinity.
+	pub fn new(point: ECPoint<C>) -> Result<Self, PointError> {
+		if point.is_identity() {
+			return Err(PointError::PointAtInfinity);
+		}
+
+		if !point.is_on_curve() {
+			return Err(PointError::NotOnCurve);
+		}
+
+		if C::curve().cofactor() != 1 && !point.is_in_subgroup() {
+			return Err(PointError::NotInSubgroup);
+		}
+
+		Ok(PublicKey { point })
+	}
+
+	/// Returns the point of the key.
+	pub fn point(&self) -> &ECPoint<C> { &self.point }
+
+	/// Returns `true` if the signature of the message hash is made by the corresponding private key.
+	pub fn verify(&self, msg_hash: &[u8; 32], sig: &Signature<C::Scalar>) -> bool {
+		let curve = C::curve();
+
+		// u1 = z * s^(-1), u2 = r * s^(-1) (mod n)
+		let w = match sig.s.invert() {
+			Some(w) => w,
+			None => return false
+		};
+		let u1 = hash_to_scalar::<C::Scalar>(msg_hash) * w;
+		let u2 = sig.r * w;
+
+		// x(u1 * G + u2 * Q) = r (mod n)
+		let point = curve.double_mul(&u1, &curve.gen_point(), &u2, &self.point);
+		!point.is_identity() && C::Scalar::reduce(&point.x.to_uint()) == sig.r
+	}
+}
+
+impl<C: Curve> Clone for PublicKey<C> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<C: Curve> Copy for PublicKey<C> { }
+
+impl<C: Curve> fmt::Debug for PublicKey<C> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		write!(f, "PublicKey{:?}", self.point)
+	}
+}
+
+impl<C: Curve> PartialEq for PublicKey<C> {
+	fn eq(&self, other: &Self) -> bool {
+		self.point == other.point
+	}
+}
+
+/// ECDSA signature: the pair of the non-zero scalars `(r, s)`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Signature<S = Scalar> {
+	r: S,
+	s: S
+}
+
+impl<S: PrimeField> Signature<S> {
+	/// Creates signature from the components, returns `None` if any of them is zero.
+	pub fn new(r: S, s: S) -> Option<Self> {
+		if r.is_zero() || s.is_zero() {
+			None
+		} else {
+			Some(Signature { r, s })
+		}
+	}
+
+	pub fn r(&self) -> &S { &self.r }
+
+	pub fn s(&self) -> &S { &self.s }
+
+	/// Returns `true` if `s` isn't greater than `n / 2` (BIP62, BIP146).
+	///
+	/// Both `(r, s)` and `(r, -s)` are valid, so only the low `s` is accepted to prevent the malleability.
+	pub fn is_low_s(&self) -> bool {
+		self.s.to_uint() <= S::modulo() / 2
+	}
+
+	/// Returns the signature with the low `s`: `(r, -s)` if `s` is greater than `n / 2`.
+	pub fn normalize_s(&self) -> Self {
+		if self.is_low_s() {
+			*self
+		} else {
+			Signature { r: self.r, s: -self.s }
+		}
+	}
+
+	/// Encodes the signature in the compact format: `r || s`, both components have the length of the order.
+	pub fn to_compact(&self) -> Vec<u8> {
+		let len = scalar_len::<S>();
+		let mut res = Vec::with_capacity(2 * len);
+		res.extend_from_slice(&self.r.to_bytes()[32 - len..]);
+		res.extend_from_slice(&self.s.to_bytes()[32 - len..]);
+		res
+	}
+
+	/// Decodes the signature from the compact format.
+	pub fn from_compact(bytes: &[u8]) -> Result<Self, SignatureError> {
+		let len = scalar_len::<S>();
+		if bytes.len() != 2 * len {
+			return Err(SignatureError::InvalidEncoding);
+		}
+
+		Self::from_components(&bytes[..len], &bytes[len..])
+	}
+
+	/// Encodes the signature in the DER format: `0x30 len 0x02 len(r) r 0x02 len(s) s`.
+	pub fn to_der(&self) -> Vec<u8> {
+		let r = der_integer(&self.r.to_bytes());
+		let s = der_integer(&self.s.to_bytes());
+
+		let mut res = Vec::with_capacity(6 + r.len() + s.len());
+		res.extend_from_slice(&[0x30, (4 + r.len() + s.len()) as u8, 0x02, r.len() as u8]);
+		res.extend_from_slice(&r);
+		res.extend_from_slice(&[0x02, s.len() as u8]);
+		res.extend_from_slice(&s);
+		res
+	}
+
+	/// Decodes the signature from the strict DER format (BIP66).
+	///
+	/// Encodings, which are valid BER but not the shortest DER (e.g. the excess padding of the integers),
+	/// are rejected with `NonCanonicalEncoding`.
+	pub fn from_der(bytes: &[u8]) -> Result<Self, SignatureError> {
+		// 0x30 len 0x02 len(r) r 0x02 len(s) s, the integers are at least one byte long
+		if bytes.len() < 8 || bytes[0] != 0x30 {
+			return Err(SignatureError::InvalidEncoding);
+		}
+
+		// Only the short form of the length is used for such sizes
+		if bytes[1] & 0x80 != 0 || bytes[3] & 0x80 != 0 {
+			return Err(SignatureError::NonCanonicalEncoding);
+		}
+
+		let r_len = bytes[3] as usize;
+		if bytes[1] as usize != bytes.len() - 2 || bytes[2] != 0x02 || 6 + r_len > bytes.len() {
+			return Err(SignatureError::InvalidEncoding);
+		}
+
+		let s_len = bytes[5 + r_len] as usize;
+		if bytes[4 + r_len] != 0x02 || 6 + r_len + s_len != bytes.len() {
+			return Err(SignatureError::InvalidEncoding);
+		}
+
+		let r = der_integer_value(&bytes[4..4 + r_len])?;
+		let s = der_integer_value(&bytes[6 + r_len..])?;
+
+		Self::from_components(r, s)
+	}
+
+	/// Creates signature from the big-endian components, checking that they are in the `[1; n)` range.
+	fn from_components(r: &[u8], s: &[u8]) -> Result<Self, SignatureError> {
+		if r.len() > 32 || s.len() > 32 {
+			return Err(SignatureError::ComponentOutOfRange);
+		}
+
+		let component = |bytes: &[u8]| {
+			let mut buf = [0u8; 32];
+			buf[32 - bytes.len()..].copy_from_slice(bytes);
+			S::from_bytes(&buf).ok_or(SignatureError::ComponentOutOfRange)
+		};
+
+		Signature::new(component(r)?, component(s)?).ok_or(SignatureError::ComponentOutOfRange)
+	}
+}
+
+/// ECDSA signature with the recovery id, which allows to restore the public key.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RecoverableSignature<S = Scalar> {
+	/// The signature.
+	sig: Signature<S>,
+
+	/// Bit 0 is the parity of `y` of the point `kG`, bit 1 is set if `x` of the point isn't less than the order.
+	recovery_id: u8
+}
+
+impl<S: PrimeField> RecoverableSignature<S> {
+	/// Creates signature with the recovery id, returns `None` if the id is greater than 3.
+	pub fn new(sig: Signature<S>, recovery_id: u8) -> Option<Self> {
+		if recovery_id > 3 {
+			None
+		} else {
+			Some(RecoverableSignature { sig, recovery_id })
+		}
+	}
+
+	/// Returns the signature without the recovery id.
+	pub fn signature(&self) -> &Signature<S> { &self.sig }
+
+	pub fn recovery_id(&self) -> u8 { self.recovery_id }
+
+	/// Restores the public key of the signer of the message hash.
+	///
+	/// The key is restored for any valid signature, so the result should be compared with the expected key.
+	pub fn recover<C: Curve<Scalar = S>>(&self, msg_hash: &[u8; 32]) -> Result<PublicKey<C>, SignatureError> {
+		let curve = C::curve();
+		let Signature { r, s } = self.sig;
+
+		// x = r or r + n, the second case is possible only if n < p
+		let mut x = r.to_uint();
+		if self.recovery_id & 2 != 0 {
+			if S::modulo() >= C::Field::modulo() || x >= C::Field::modulo() - S::modulo() {
+				return Err(SignatureError::RecoveryFailed);
+			}
+			x = x + S::modulo();
+		}
+
+		let x = C::Field::from_uint(&x).ok_or(SignatureError::RecoveryFailed)?;
+		let point = curve.lift_x(&x, self.recovery_id & 1 != 0).map_err(|_| SignatureError::RecoveryFailed)?;
+
+		// Q = r^(-1) * (sR - zG)
+		let rinv = r.invert().ok_or(SignatureError::RecoveryFailed)?;
+		let u1 = -hash_to_scalar::<S>(msg_hash) * rinv;
+		let u2 = s * rinv;
+		let key = curve.double_mul(&u1, &curve.gen_point(), &u2, &point);
+
+		PublicKey::new(key).map_err(|_| SignatureError::RecoveryFailed)
+	}
+
+	/// Encodes the signature in the compact format: `r || s || recovery_id` (65 bytes for the 256-bit order).
+	pub fn to_compact(&self) -> Vec<u8> {
+		let mut res = self.sig.to_compact();
+		res.push(self.recovery_id);
+		res
+	}
+
+	/// Decodes the signature from the compact format.
+	pub fn from_compact(bytes: &[u8]) -> Result<Self, SignatureError> {
+		let (&recovery_id, sig) = bytes.split_last().ok_or(SignatureError::InvalidEncoding)?;
+		let sig = Signature::from_compact(sig)?;
+
+		RecoverableSignature::new(sig, recovery_id).ok_or(SignatureError::InvalidRecoveryId)
+	}
+}
+
+/// Error of the signature decoding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SignatureError {
+	/// Encoding has the wrong structure or length.
+	InvalidEncoding,
+
+	/// Encoding is valid, but not canonical: the integers have the excess padding, are negative
+	/// or the lengths use the long form.
+	NonCanonicalEncoding,
+
+	/// Signature component is zero or isn't less than the order.
+	ComponentOutOfRange,
+
+	/// Recovery id is greater than 3.
+	InvalidRecoveryId,
+
+	/// Public key can't be restored from the signature.
+	RecoveryFailed
+}
+
+impl fmt::Display for SignatureError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			SignatureError::InvalidEncoding => write!(f, "Signature encoding is invalid."),
+			SignatureError::NonCanonicalEncoding => write!(f, "Signature encoding is not canonical."),
+			SignatureError::ComponentOutOfRange => write!(f, "Signature component is out of the range."),
+			SignatureError::InvalidRecoveryId => write!(f, "Signature recovery id is invalid."),
+			SignatureError::RecoveryFailed => write!(f, "Public key can't be recovered from the signature.")
+		}
+	}
+}
+
+impl Error for SignatureError { }
+
+/// Returns the length of the scalar in bytes.
+fn scalar_len<S: PrimeField>() -> usize {
+	S::NUM_BITS.div_ceil(8)
+}
+
+/// Returns the minimal DER encoding of the positive integer: without the leading zeros,
+/// but with the zero byte if the most significant bit is set.
+fn der_integer(bytes: &[u8]) -> Vec<u8> {
+	let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len() - 1);
+	let mut res = Vec::with_capacity(bytes.len() - start + 1);
+
+	if bytes[start] & 0x80 != 0 {
+		res.push(0);
+	}
+	res.extend_from_slice(&bytes[start..]);
+	res
+}
+
+/// Returns the value of the DER integer, checking that it's the minimal encoding of the positive number.
+fn der_integer_value(bytes: &[u8]) -> Result<&[u8], SignatureError> {
+	match bytes {
+		[] => Err(SignatureError::InvalidEncoding),
+
+		// Negative number
+		[first, ..] if first & 0x80 != 0 => Err(SignatureError::NonCanonicalEncoding),
+
+		// Excess zero padding
+		[0, second, ..] if second & 0x80 == 0 => Err(SignatureError::NonCanonicalEncoding),
+		[0, rest @ ..] => Ok(rest),
+		_ => Ok(bytes)
+	}
+}
+
+/// Converts the message hash into the scalar, using its leftmost bits if the order is shorter than the hash.
+fn hash_to_scalar<S: PrimeField>(msg_hash: &[u8; 32]) -> S {
+	S::reduce(&bits_to_uint::<S>(msg_hash))
+}
+
+/// Returns the leftmost bits of the bytes, as many as the order of `S` has (`bits2int` of RFC 6979).
+pub(crate) fn bits_to_uint<S: PrimeField>(bytes: &[u8; 32]) -> Uint256 {
+	let num = Uint256::from_be_bytes(bytes);
+	if S::NUM_BITS >= 256 {
+		return num;
+	}
+
+	// num >> (256 - NUM_BITS)
+	let shift = 256 - S::NUM_BITS;
+	let mut raw = [0u64; 4];
+	for i in 0..S::NUM_BITS {
+		raw[i / 64] |= (num.bit(i + shift) as u64) << (i % 64);
+	}
+
+	Uint256::from_raw(raw)
+}
+
+#[cfg(test)]
+use crypto::field::{FieldModulus, Fp};
+
+#[test]
+fn test_hash_to_scalar() {
+	#[derive(Debug)]
+	struct M;
+
+	impl FieldModulus for M {
+		// 2^224 - 2^96 + 1
+		const MODULO: Uint256 = Uint256([1, 0xffffffff00000000, 0xffffffffffffffff, 0xffffffff]);
+	}
+
+	let mut hash = [0u8; 32];
+	hash[0] = 0x80;
+	hash[27] = 0x01;
+	hash[31] = 0xff;
+
+	// The hash is truncated to the leftmost 224 bits
+	assert_eq!(hash_to_scalar::<Fp<M>>(&hash).to_uint(), Uint256::from_raw([0x0000000000000001, 0, 0, 0x80000000]));
+	assert_eq!(hash_to_scalar::<Scalar>(&hash).to_uint(), Uint256::from_be_bytes(&hash));
+}
@@ -0,0 +1,30 @@
+use utils::bignum::Uint256;
+use crypto::field::{FieldModulus, Fp};
+
+/// The modulo of the Curve25519 base field: `p = 2^255 - 19`.
+pub const ED25519_P: Uint256 = Uint256([0xffffffffffffffed, 0xffffffffffffffff, 0xffffffffffffffff, 0x7fffffffffffffff]);
+
+/// The order of the Ed25519 base point: `L = 2^252 + 27742317777372353535851937790883648493`.
+pub const ED25519_L: Uint256 = Uint256([0x5812631a5cf5d3ed, 0x14def9dea2f79cd6, 0x0000000000000000, 0x1000000000000000]);
+
+/// The modulo of the Curve25519 base field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Curve25519Base;
+
+impl FieldModulus for Curve25519Base {
+	const MODULO: Uint256 = ED25519_P;
+}
+
+/// The modulo of the Ed25519 scalar field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Ed25519Order;
+
+impl FieldModulus for Ed25519Order {
+	const MODULO: Uint256 = ED25519_L;
+}
+
+/// Element of the Curve25519 base field (coordinate of the Edwards point).
+pub type FieldElement = Fp<Curve25519Base>;
+
+/// Element of the Ed25519 scalar field (multiplier of the Edwards point).
+pub type Scalar = Fp<Ed25519Order>;
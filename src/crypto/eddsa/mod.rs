@@ -0,0 +1,7 @@
+mod field;
+mod point;
+mod signature;
+
+pub use self::field::{FieldElement, Scalar, Curve25519Base, Ed25519Order, ED25519_P, ED25519_L};
+pub use self::point::{EdwardsPoint, PointError};
+pub use self::signature::{PrivateKey, PublicKey, Signature, SignatureError};
@@ -0,0 +1,302 @@
+use utils::bignum::{Uint256, Zero, One};
+use crypto::field::PrimeField;
+use super::{FieldElement, Scalar};
+use std::ops::{Add, Sub, Mul, Neg};
+use std::error::Error;
+use std::fmt;
+
+/// The `d` parameter of the curve: `-121665 / 121666`.
+const D: FieldElement = FieldElement::new(Uint256([0x75eb4dca135978a3, 0x00700a4d4141d8ab, 0x8cc740797779e898, 0x52036cee2b6ffe73]));
+
+/// The `x` coordinate of the base point.
+const BASE_X: FieldElement = FieldElement::new(Uint256([0xc9562d608f25d51a, 0x692cc7609525a7b2, 0xc0a4e231fdd6dc5c, 0x216936d3cd6e53fe]));
+
+/// The `y` coordinate of the base point: `4 / 5`.
+const BASE_Y: FieldElement = FieldElement::new(Uint256([0x6666666666666658, 0x6666666666666666, 0x6666666666666666, 0x6666666666666666]));
+
+/// Point on the twisted Edwards curve `-x^2 + y^2 = 1 + dx^2y^2` (edwards25519, RFC 8032).
+///
+/// The point is stored in the extended coordinates `(X : Y : Z : T)`, where `x = X/Z`, `y = Y/Z` and `xy = T/Z`.
+/// The addition formulas are complete, so the identity and the doubling don't need the special cases.
+#[derive(Clone, Copy)]
+pub struct EdwardsPoint {
+	x: FieldElement,
+	y: FieldElement,
+	z: FieldElement,
+	t: FieldElement
+}
+
+impl EdwardsPoint {
+	/// Creates point from the affine coordinates.
+	///
+	/// Coordinates aren't validated, use `decode` for the untrusted input.
+	pub fn new(x: FieldElement, y: FieldElement) -> Self {
+		EdwardsPoint { x, y, z: FieldElement::one(), t: x * y }
+	}
+
+	/// Creates the neutral point `(0, 1)`.
+	pub fn identity() -> Self {
+		EdwardsPoint::new(FieldElement::zero(), FieldElement::one())
+	}
+
+	/// Returns the base point `B` of the Ed25519, which generates the subgroup of the order `L`.
+	pub fn base_point() -> Self {
+		EdwardsPoint::new(BASE_X, BASE_Y)
+	}
+
+	/// Returns the affine coordinates `(x, y)` of the point.
+	pub fn to_affine(&self) -> (FieldElement, FieldElement) {
+		// Z is never zero for the complete formulas
+		let z_inv = self.z.invert().expect("Z coordinate of the Edwards point is zero.");
+		(self.x * z_inv, self.y * z_inv)
+	}
+
+	/// Returns `true` if the point is the neutral point.
+	pub fn is_identity(&self) -> bool {
+		self.x.is_zero() && self.y == self.z
+	}
+
+	/// Returns `true` if the point satisfies the curve equation.
+	pub fn is_on_curve(&self) -> bool {
+		// -X^2 Z^2 + Y^2 Z^2 = Z^4 + d X^2 Y^2 and XY = TZ
+		let (x2, y2, z2) = (self.x.square(), self.y.square(), self.z.square());
+		(y2 - x2) * z2 == z2.square() + D * x2 * y2 && self.x * self.y == self.t * self.z
+	}
+
+	/// Returns `true` if the point belongs to the subgroup generated by the base point (`LP = O`).
+	pub fn is_in_subgroup(&self) -> bool {
+		// LP = O <=> (L - 1)P = -P
+		self.mul_vartime(&-Scalar::one()) == -*self
+	}
+
+	/// Returns `true` if the point belongs to the torsion subgroup of the order 8 (`8P = O`).
+	pub fn is_small_order(&self) -> bool {
+		self.mul_by_cofactor().is_identity()
+	}
+
+	/// Multiplies the point by the cofactor 8.
+	pub fn mul_by_cofactor(&self) -> Self {
+		self.double().double().double()
+	}
+
+	/// Doubles the point ("dbl-2008-hwcd").
+	pub fn double(&self) -> Self {
+		let a = self.x.square();
+		let b = self.y.square();
+		let c = self.z.square() + self.z.square();
+		let h = a + b;
+		let e = h - (self.x + self.y).square();
+		let g = a - b;
+		let f = c + g;
+
+		EdwardsPoint { x: e * f, y: g * h, z: f * g, t: e * h }
+	}
+
+	/// Multiplies the base point by the scalar in the constant time.
+	pub fn mul_base(k: &Scalar) -> Self {
+		EdwardsPoint::base_point() * *k
+	}
+
+	/// Multiplies the point by the scalar in the variable time.
+	///
+	/// Faster than the multiplication operator, but leaks the scalar through the timing,
+	/// so it should be used only with the public scalars (e.g. in the signature verification).
+	pub fn mul_vartime(&self, k: &Scalar) -> Self {
+		EdwardsPoint::double_mul(k, self, &Scalar::zero(), &EdwardsPoint::identity())
+	}
+
+	/// Computes `aP + bQ` in the variable time (Shamir's trick).
+	pub fn double_mul(a: &Scalar, p: &EdwardsPoint, b: &Scalar, q: &EdwardsPoint) -> Self {
+		let (a, b) = (a.to_uint(), b.to_uint());
+		let pq = *p + *q;
+		let mut res = EdwardsPoint::identity();
+
+		for i in (0..a.bits().max(b.bits())).rev() {
+			res = res.double();
+
+			match (a.bit(i), b.bit(i)) {
+				(true, true) => res = res + pq,
+				(true, false) => res = res + *p,
+				(false, true) => res = res + *q,
+				(false, false) => ()
+			}
+		}
+
+		res
+	}
+
+	/// Encodes the point as 32 bytes (RFC 8032, section 5.1.2): the little-endian `y`
+	/// with the lowest bit of `x` in the most significant bit.
+	pub fn encode(&self) -> [u8; 32] {
+		let (x, y) = self.to_affine();
+		let mut res = uint_to_le_bytes(&y.to_uint());
+		res[31] |= (x.to_uint().is_odd() as u8) << 7;
+		res
+	}
+
+	/// Decodes the point (RFC 8032, section 5.1.3), checking that it's a valid point of the curve.
+	///
+	/// The point isn't checked to be in the subgroup of the base point.
+	pub fn decode(bytes: &[u8; 32]) -> Result<Self, PointError> {
+		let odd = bytes[31] >> 7 == 1;
+		let mut y_bytes = *bytes;
+		y_bytes[31] &= 0x7f;
+
+		let y = FieldElement::from_uint(&le_bytes_to_uint(&y_bytes)).ok_or(PointError::CoordinateOutOfRange)?;
+
+		// x^2 = (y^2 - 1) / (dy^2 + 1), the denominator isn't zero since `d` isn't a square
+		let y2 = y.square();
+		let x2 = (y2 - FieldElement::one()) * (D * y2 + FieldElement::one()).invert().expect("d is not a square.");
+		let x = x2.sqrt().ok_or(PointError::NotOnCurve)?;
+
+		// Negative zero isn't a valid encoding
+		if x.is_zero() && odd {
+			return Err(PointError::NegativeZero);
+		}
+
+		let x = FieldElement::conditional_select(&x, &-x, x.to_uint().is_odd() != odd);
+		Ok(EdwardsPoint::new(x, y))
+	}
+
+	/// Swaps `a` and `b` if `choice` is `true`, without branching on `choice`.
+	fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+		let (a_new, b_new) = (
+			EdwardsPoint {
+				x: FieldElement::conditional_select(&a.x, &b.x, choice),
+				y: FieldElement::conditional_select(&a.y, &b.y, choice),
+				z: FieldElement::conditional_select(&a.z, &b.z, choice),
+				t: FieldElement::conditional_select(&a.t, &b.t, choice)
+			},
+			EdwardsPoint {
+				x: FieldElement::conditional_select(&b.x, &a.x, choice),
+				y: FieldElement::conditional_select(&b.y, &a.y, choice),
+				z: FieldElement::conditional_select(&b.z, &a.z, choice),
+				t: FieldElement::conditional_select(&b.t, &a.t, choice)
+			}
+		);
+
+		*a = a_new;
+		*b = b_new;
+	}
+}
+
+/// Converts the little-endian bytes into the number.
+pub(crate) fn le_bytes_to_uint(bytes: &[u8; 32]) -> Uint256 {
+	let mut be = *bytes;
+	be.reverse();
+	Uint256::from_be_bytes(&be)
+}
+
+/// Converts the number into the little-endian bytes.
+pub(crate) fn uint_to_le_bytes(value: &Uint256) -> [u8; 32] {
+	let mut res = value.to_be_bytes();
+	res.reverse();
+	res
+}
+
+impl fmt::Debug for EdwardsPoint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		let (x, y) = self.to_affine();
+		write!(f, "({:?}, {:?})", x, y)
+	}
+}
+
+impl PartialEq for EdwardsPoint {
+	fn eq(&self, other: &Self) -> bool {
+		// X1/Z1 = X2/Z2 and Y1/Z1 = Y2/Z2
+		self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+	}
+}
+
+impl Eq for EdwardsPoint { }
+
+impl Add for EdwardsPoint {
+	type Output = Self;
+
+	/// Adds the points ("add-2008-hwcd-3"), the formula is complete.
+	fn add(self, other: Self) -> Self {
+		let a = (self.y - self.x) * (other.y - other.x);
+		let b = (self.y + self.x) * (other.y + other.x);
+		let c = self.t * (D + D) * other.t;
+		let d = (self.z + self.z) * other.z;
+		let (e, f, g, h) = (b - a, d - c, d + c, b + a);
+
+		EdwardsPoint { x: e * f, y: g * h, z: f * g, t: e * h }
+	}
+}
+
+impl Neg for EdwardsPoint {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		EdwardsPoint { x: -self.x, y: self.y, z: self.z, t: -self.t }
+	}
+}
+
+impl Sub for EdwardsPoint {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		self + -other
+	}
+}
+
+impl Mul<Scalar> for EdwardsPoint {
+	type Output = Self;
+
+	/// Multiplies the point by the scalar in the constant time (Montgomery ladder).
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn mul(self, other: Scalar) -> Self {
+		let k = other.to_uint();
+		let (mut r0, mut r1) = (EdwardsPoint::identity(), self);
+
+		// Invariant: R1 - R0 = P, the number of iterations doesn't depend on the scalar
+		for i in (0..Scalar::NUM_BITS).rev() {
+			let bit = k.bit(i);
+
+			EdwardsPoint::conditional_swap(&mut r0, &mut r1, bit);
+			r1 = r0 + r1;
+			r0 = r0.double();
+			EdwardsPoint::conditional_swap(&mut r0, &mut r1, bit);
+		}
+
+		r0
+	}
+}
+
+/// Error of the point decoding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PointError {
+	/// The `y` coordinate isn't less than the field modulo.
+	CoordinateOutOfRange,
+
+	/// There is no `x` coordinate for the `y` coordinate.
+	NotOnCurve,
+
+	/// The `x` coordinate is zero, but the sign bit is set.
+	NegativeZero
+}
+
+impl fmt::Display for PointError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			PointError::CoordinateOutOfRange => write!(f, "Point coordinate is out of the field range."),
+			PointError::NotOnCurve => write!(f, "Point is not on the curve."),
+			PointError::NegativeZero => write!(f, "Point encoding has the sign bit of the zero coordinate.")
+		}
+	}
+}
+
+impl Error for PointError { }
+
+#[test]
+fn test_base_point() {
+	let b = EdwardsPoint::base_point();
+	assert!(b.is_on_curve());
+	assert!(b.is_in_subgroup());
+	assert!(!b.is_small_order());
+
+	// d = -121665 / 121666
+	assert_eq!(D * FieldElement::from(121666), -FieldElement::from(121665));
+	assert_eq!(BASE_Y * FieldElement::from(5), FieldElement::from(4));
+}
@@ -0,0 +1,193 @@
+use utils::bignum::{Uint256, One};
+use crypto::field::PrimeField;
+use crypto::hash::{Hasher, Sha512, sha512};
+use super::{EdwardsPoint, PointError, Scalar};
+use super::point::{le_bytes_to_uint, uint_to_le_bytes};
+use std::error::Error;
+use std::fmt;
+
+/// Ed25519 private key (RFC 8032, section 5.1.5): the 32-byte seed, which is expanded by SHA-512
+/// into the secret scalar and the prefix of the nonces.
+#[derive(Clone)]
+pub struct PrivateKey {
+	/// The original 32 bytes of the key.
+	seed: [u8; 32],
+
+	/// The secret scalar `s`, the clamped lower half of the seed hash.
+	s: Scalar,
+
+	/// The upper half of the seed hash, which is mixed into the nonces.
+	prefix: [u8; 32],
+
+	/// The public key `A = sB`.
+	public: PublicKey
+}
+
+impl PrivateKey {
+	/// Creates private key from the 32 bytes, any bytes are the valid key.
+	pub fn from_bytes(seed: &[u8; 32]) -> Self {
+		let h = sha512(seed);
+		let mut lower = [0u8; 32];
+		let mut prefix = [0u8; 32];
+		lower.copy_from_slice(&h[..32]);
+		prefix.copy_from_slice(&h[32..]);
+
+		// Clear the lowest 3 bits and the highest bit, set the second highest bit
+		lower[0] &= 0xf8;
+		lower[31] &= 0x7f;
+		lower[31] |= 0x40;
+
+		// The clamped number may exceed `L`, but `B` has the order `L`, so it's reduced without changing `sB`
+		let s = Scalar::reduce(&le_bytes_to_uint(&lower));
+		let public = PublicKey::from_point(EdwardsPoint::mul_base(&s));
+
+		PrivateKey { seed: *seed, s, prefix, public }
+	}
+
+	/// Returns the original 32 bytes of the key.
+	pub fn to_bytes(&self) -> [u8; 32] { self.seed }
+
+	/// Returns the public key `A = sB`.
+	pub fn public_key(&self) -> PublicKey { self.public }
+
+	/// Signs the message by Ed25519 (RFC 8032, section 5.1.6).
+	///
+	/// The nonce is derived from the key and the message, so the signature is deterministic.
+	pub fn sign(&self, msg: &[u8]) -> Signature {
+		// r = SHA-512(prefix || M) (mod L)
+		let mut hasher = Sha512::new();
+		hasher.update(&self.prefix);
+		hasher.update(msg);
+		let r = hash_to_scalar(hasher);
+		let r_point = EdwardsPoint::mul_base(&r).encode();
+
+		// S = r + k * s (mod L)
+		let k = challenge(&r_point, &self.public.encoded, msg);
+		Signature { r: r_point, s: r + k * self.s }
+	}
+}
+
+impl fmt::Debug for PrivateKey {
+	/// Doesn't output the secret key.
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		write!(f, "PrivateKey(..)")
+	}
+}
+
+/// Ed25519 public key: the point `A = sB`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PublicKey {
+	/// The point of the key.
+	point: EdwardsPoint,
+
+	/// The encoding of the point, which is hashed by the signing and the verification.
+	encoded: [u8; 32]
+}
+
+impl PublicKey {
+	/// Decodes the key, checking that it's a valid point of the curve.
+	pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, PointError> {
+		let point = EdwardsPoint::decode(bytes)?;
+		Ok(PublicKey { point, encoded: *bytes })
+	}
+
+	fn from_point(point: EdwardsPoint) -> Self {
+		PublicKey { point, encoded: point.encode() }
+	}
+
+	/// Returns the encoding of the key.
+	pub fn to_bytes(&self) -> [u8; 32] { self.encoded }
+
+	/// Returns the point of the key.
+	pub fn point(&self) -> &EdwardsPoint { &self.point }
+
+	/// Verifies the Ed25519 signature of the message (RFC 8032, section 5.1.7).
+	///
+	/// Uses the cofactored equation `8SB = 8R + 8kA`, so the result doesn't depend on the small order
+	/// components of `R` and `A` and agrees with the batch verification.
+	pub fn verify(&self, msg: &[u8], sig: &Signature) -> bool {
+		let r = match EdwardsPoint::decode(&sig.r) {
+			Ok(r) => r,
+			Err(_) => return false
+		};
+		let k = challenge(&sig.r, &self.encoded, msg);
+
+		// 8(SB - kA - R) = O
+		let res = EdwardsPoint::double_mul(&sig.s, &EdwardsPoint::base_point(), &-k, &self.point) - r;
+		res.is_small_order()
+	}
+}
+
+/// Ed25519 signature: the encoded nonce point `R` and the scalar `S`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Signature {
+	r: [u8; 32],
+	s: Scalar
+}
+
+impl Signature {
+	/// Decodes the signature from `R || S`, checking that `S` is less than `L`.
+	///
+	/// The point `R` is decoded by the verification, the signature with the invalid `R` is never valid.
+	pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self, SignatureError> {
+		let mut r = [0u8; 32];
+		let mut s = [0u8; 32];
+		r.copy_from_slice(&bytes[..32]);
+		s.copy_from_slice(&bytes[32..]);
+
+		let s = Scalar::from_uint(&le_bytes_to_uint(&s)).ok_or(SignatureError::ScalarOutOfRange)?;
+		Ok(Signature { r, s })
+	}
+
+	/// Encodes the signature as `R || S`.
+	pub fn to_bytes(&self) -> [u8; 64] {
+		let mut res = [0u8; 64];
+		res[..32].copy_from_slice(&self.r);
+		res[32..].copy_from_slice(&uint_to_le_bytes(&self.s.to_uint()));
+		res
+	}
+
+	/// Returns the encoding of the nonce point `R`.
+	pub fn r(&self) -> &[u8; 32] { &self.r }
+
+	pub fn s(&self) -> &Scalar { &self.s }
+}
+
+/// Error of the signature decoding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SignatureError {
+	/// The scalar `S` isn't less than `L`.
+	ScalarOutOfRange
+}
+
+impl fmt::Display for SignatureError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			SignatureError::ScalarOutOfRange => write!(f, "Signature scalar is out of the range.")
+		}
+	}
+}
+
+impl Error for SignatureError { }
+
+/// Computes the challenge `k = SHA-512(R || A || M) (mod L)`.
+fn challenge(r: &[u8; 32], a: &[u8; 32], msg: &[u8]) -> Scalar {
+	let mut hasher = Sha512::new();
+	hasher.update(r);
+	hasher.update(a);
+	hasher.update(msg);
+	hash_to_scalar(hasher)
+}
+
+/// Finishes the hashing and reduces the 512-bit little-endian digest by `L`.
+fn hash_to_scalar(hasher: Sha512) -> Scalar {
+	let h = hasher.finalize();
+	let mut lower = [0u8; 32];
+	let mut upper = [0u8; 32];
+	lower.copy_from_slice(&h[..32]);
+	upper.copy_from_slice(&h[32..]);
+
+	// h = upper * 2^256 + lower, 2^256 = (2^256 - 1) + 1 (mod L)
+	let r = Scalar::reduce(&Uint256::max()) + Scalar::one();
+	Scalar::reduce(&le_bytes_to_uint(&upper)) * r + Scalar::reduce(&le_bytes_to_uint(&lower))
+}
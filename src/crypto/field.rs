@@ -0,0 +1,510 @@
+use utils::bignum::{Uint256, Zero, One};
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::marker::PhantomData;
+use std::fmt;
+
+/// Finite field of the prime order.
+pub trait PrimeField: Sized + Copy + Eq + fmt::Debug + Zero + One
+	+ Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+	/// Number of significant bits in the modulo.
+	const NUM_BITS: usize;
+
+	/// Returns the modulo of the field.
+	fn modulo() -> Uint256;
+
+	/// Creates element from the number, returns `None` if the number isn't less than the modulo.
+	fn from_uint(value: &Uint256) -> Option<Self>;
+
+	/// Creates element from the number, reducing it by the modulo.
+	fn reduce(value: &Uint256) -> Self;
+
+	/// Returns the element as a number in the `[0; modulo)` range.
+	fn to_uint(&self) -> Uint256;
+
+	/// Returns `a` if `choice` is `false` and `b` if `choice` is `true`, without branching on `choice`.
+	fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self;
+
+	/// Creates element from the big-endian bytes, returns `None` if the number isn't less than the modulo.
+	fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+		Self::from_uint(&Uint256::from_be_bytes(bytes))
+	}
+
+	/// Returns the element as the big-endian bytes.
+	fn to_bytes(&self) -> [u8; 32] {
+		self.to_uint().to_be_bytes()
+	}
+
+	/// Squares the element.
+	fn square(&self) -> Self {
+		*self * *self
+	}
+
+	/// Raises the element to the power of `exp`.
+	fn pow(&self, exp: &Uint256) -> Self {
+		let mut res = Self::one();
+
+		// Left-to-right binary exponentiation
+		for i in (0..exp.bits()).rev() {
+			res = res.square();
+
+			if exp.bit(i) {
+				res = res * *self;
+			}
+		}
+
+		res
+	}
+
+	/// Returns multiplicative inverse of the element or `None` if the element is zero.
+	fn invert(&self) -> Option<Self> {
+		if self.is_zero() {
+			return None;
+		}
+
+		// Fermat's little theorem: a^(-1) = a^(p - 2) (mod p)
+		Some(self.pow(&(Self::modulo() - Uint256::from(2))))
+	}
+
+	/// Returns `true` if the element is a square of some element of the field.
+	fn is_quadratic_residue(&self) -> bool {
+		// Euler's criterion: a^((p - 1) / 2) = 1 (mod p)
+		self.is_zero() || self.pow(&((Self::modulo() - Uint256::one()) / 2)).is_one()
+	}
+
+	/// Returns square root of the element or `None` if it doesn't exist.
+	///
+	/// Any of two roots may be returned.
+	fn sqrt(&self) -> Option<Self> {
+		if !self.is_quadratic_residue() {
+			return None;
+		}
+
+		let p = Self::modulo();
+		let one = Uint256::one();
+
+		// p = 3 (mod 4): r = a^((p + 1) / 4)
+		if p.raw()[0] & 3 == 3 {
+			return Some(self.pow(&((p + one) / 4)));
+		}
+
+		// Tonelli-Shanks algorithm
+
+		// p - 1 = q * 2^s, where `q` is odd
+		let (mut q, mut s) = (p - one, 0);
+		while q.is_even() {
+			q = q / 2;
+			s += 1;
+		}
+
+		// Find quadratic non-residue `z`
+		let mut z = Self::one() + Self::one();
+		while z.is_quadratic_residue() {
+			z = z + Self::one();
+		}
+
+		let mut m = s;
+		let mut c = z.pow(&q);
+		let mut t = self.pow(&q);
+		let mut r = self.pow(&((q + one) / 2));
+
+		while !t.is_zero() && !t.is_one() {
+			// Find the least `i` (0 < i < m) such that t^(2^i) = 1
+			let (mut i, mut tt) = (0, t);
+			while !tt.is_one() {
+				tt = tt.square();
+				i += 1;
+			}
+
+			// b = c^(2^(m - i - 1))
+			let mut b = c;
+			for _ in 0..m - i - 1 {
+				b = b.square();
+			}
+
+			m = i;
+			c = b.square();
+			t = t * c;
+			r = r * b;
+		}
+
+		Some(r)
+	}
+}
+
+/// Returns `true` if the modulo of the field is an odd prime number, checked by the Miller-Rabin test
+/// with the given bases, every base that isn't a witness of compositeness quarters the error probability.
+///
+/// The field arithmetic is still the modular arithmetic if the modulo is odd, but not prime,
+/// so the test is used to validate the moduli of the curves, which are defined at the runtime.
+/// Such moduli can be chosen to pass the fixed bases, so the bases shouldn't be known in advance.
+/// Returns `false` if there are no bases, the test doesn't prove anything then.
+pub fn is_prime_modulo<F: PrimeField>(bases: &[Uint256]) -> bool {
+	let p = F::modulo();
+	let one = Uint256::one();
+
+	// The Montgomery form requires the odd modulo
+	if bases.is_empty() || p.is_even() || p == one {
+		return false;
+	}
+
+	// p - 1 = d * 2^s, where `d` is odd
+	let (mut d, mut s) = (p - one, 0);
+	while d.is_even() {
+		d = d / 2;
+		s += 1;
+	}
+
+	let minus_one = -F::one();
+
+	'rounds: for base in bases {
+		let a = F::reduce(base);
+
+		// These bases pass for any modulo
+		if a.is_zero() || a.is_one() || a == minus_one {
+			continue;
+		}
+
+		// Prime `p` gives either a^d = 1 or a^(d * 2^r) = -1 for some `r < s`
+		let mut x = a.pow(&d);
+		if x.is_one() || x == minus_one {
+			continue;
+		}
+
+		for _ in 1..s {
+			x = x.square();
+			if x == minus_one {
+				continue 'rounds;
+			}
+		}
+
+		return false;
+	}
+
+	true
+}
+
+/// Modulo of the prime field.
+pub trait FieldModulus {
+	/// The modulo, should be an odd prime number.
+	const MODULO: Uint256;
+}
+
+/// Element of the prime field, which modulo is defined by `M`.
+///
+/// Element is stored in the Montgomery form `a * R (mod p)`, where `R = 2^256`.
+pub struct Fp<M>(Uint256, PhantomData<M>);
+
+impl<M: FieldModulus> Fp<M> {
+	/// Montgomery constant: `-p^(-1) (mod 2^64)`.
+	const INV: u64 = mont_inv(M::MODULO.0[0]);
+
+	/// Montgomery constant: `R^2 (mod p)`, used for conversion into the Montgomery form.
+	const R2: Uint256 = mont_r2(&M::MODULO);
+
+	/// One in the Montgomery form: `R (mod p)`.
+	const ONE: Uint256 = Uint256(mont_mul(&[1, 0, 0, 0], &Self::R2.0, &M::MODULO.0, Self::INV));
+
+	/// Creates element from the number, reducing it by the modulo.
+	pub const fn new(value: Uint256) -> Self {
+		// (a * R^2) * R^(-1) = a * R (mod p)
+		Fp(Uint256(mont_mul(&value.0, &Self::R2.0, &M::MODULO.0, Self::INV)), PhantomData)
+	}
+}
+
+impl<M> Clone for Fp<M> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<M> Copy for Fp<M> { }
+
+impl<M> PartialEq for Fp<M> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<M> Eq for Fp<M> { }
+
+impl<M: FieldModulus> fmt::Debug for Fp<M> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		write!(f, "{:?}", self.to_uint())
+	}
+}
+
+impl<M: FieldModulus> From<u64> for Fp<M> {
+	fn from(num: u64) -> Self {
+		Fp::new(Uint256::from(num))
+	}
+}
+
+impl<M: FieldModulus> PrimeField for Fp<M> {
+	const NUM_BITS: usize = bits(&M::MODULO);
+
+	fn modulo() -> Uint256 {
+		M::MODULO
+	}
+
+	fn from_uint(value: &Uint256) -> Option<Self> {
+		if *value < M::MODULO {
+			Some(Fp::new(*value))
+		} else {
+			None
+		}
+	}
+
+	fn reduce(value: &Uint256) -> Self {
+		Fp::new(*value)
+	}
+
+	fn to_uint(&self) -> Uint256 {
+		// (a * R) * R^(-1) = a (mod p)
+		Uint256(mont_mul(&(self.0).0, &[1, 0, 0, 0], &M::MODULO.0, Self::INV))
+	}
+
+	fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+		Fp(Uint256(select(&(b.0).0, &(a.0).0, choice)), PhantomData)
+	}
+}
+
+impl<M: FieldModulus> Add for Fp<M> {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		let (sum, carry) = add_carry(&(self.0).0, &(other.0).0);
+		let (diff, borrow) = sub_borrow(&sum, &M::MODULO.0);
+
+		// Subtract the modulo if `a + b >= p`
+		Fp(Uint256(select(&sum, &diff, carry == 0 && borrow == 1)), PhantomData)
+	}
+}
+
+impl<M: FieldModulus> Sub for Fp<M> {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		let (diff, borrow) = sub_borrow(&(self.0).0, &(other.0).0);
+
+		// Add the modulo if `a < b`
+		let (sum, _) = add_carry(&diff, &M::MODULO.0);
+		Fp(Uint256(select(&sum, &diff, borrow == 1)), PhantomData)
+	}
+}
+
+impl<M: FieldModulus> Mul for Fp<M> {
+	type Output = Self;
+
+	fn mul(self, other: Self) -> Self {
+		Fp(Uint256(mont_mul(&(self.0).0, &(other.0).0, &M::MODULO.0, Self::INV)), PhantomData)
+	}
+}
+
+impl<M: FieldModulus> Div for Fp<M> {
+	type Output = Self;
+
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn div(self, other: Self) -> Self {
+		match other.invert() {
+			Some(inv) => self * inv,
+			None => panic!("The divisor should not be zero.")
+		}
+	}
+}
+
+impl<M: FieldModulus> Neg for Fp<M> {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Self::zero() - self
+	}
+}
+
+impl<M: FieldModulus> Zero for Fp<M> {
+	fn zero() -> Self {
+		Fp(Uint256::zero(), PhantomData)
+	}
+
+	fn is_zero(&self) -> bool {
+		// Don't leave the loop early to not leak the element through the timing
+		(self.0).0.iter().fold(0, |acc, digit| acc | digit) == 0
+	}
+}
+
+impl<M: FieldModulus> One for Fp<M> {
+	fn one() -> Self {
+		Fp(Self::ONE, PhantomData)
+	}
+
+	fn is_one(&self) -> bool {
+		self.0 == Self::ONE
+	}
+}
+
+/// Adds two numbers, returns `(result, carry)`.
+const fn add_carry(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+	let mut res = [0u64; 4];
+	let mut carry = 0u128;
+	let mut i = 0;
+
+	while i < 4 {
+		let s = a[i] as u128 + b[i] as u128 + carry;
+		res[i] = s as u64;
+		carry = s >> 64;
+		i += 1;
+	}
+
+	(res, carry as u64)
+}
+
+/// Subtracts two numbers, returns `(result, borrow)`.
+const fn sub_borrow(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+	let mut res = [0u64; 4];
+	let mut borrow = 0u64;
+	let mut i = 0;
+
+	while i < 4 {
+		let (d, o1) = a[i].overflowing_sub(b[i]);
+		let (d, o2) = d.overflowing_sub(borrow);
+		res[i] = d;
+		borrow = (o1 | o2) as u64;
+		i += 1;
+	}
+
+	(res, borrow)
+}
+
+/// Returns `a` if `choice` is `true` and `b` otherwise, without branching on `choice`.
+const fn select(a: &[u64; 4], b: &[u64; 4], choice: bool) -> [u64; 4] {
+	let mask = 0u64.wrapping_sub(choice as u64);
+	let mut res = [0u64; 4];
+	let mut i = 0;
+
+	while i < 4 {
+		res[i] = (a[i] & mask) | (b[i] & !mask);
+		i += 1;
+	}
+
+	res
+}
+
+/// Counts significant bits in the number.
+const fn bits(a: &Uint256) -> usize {
+	let mut i = 4;
+
+	while i > 0 {
+		if a.0[i - 1] != 0 {
+			return i * 64 - a.0[i - 1].leading_zeros() as usize;
+		}
+		i -= 1;
+	}
+
+	0
+}
+
+/// Computes `-p^(-1) (mod 2^64)` by the Newton's method.
+const fn mont_inv(p: u64) -> u64 {
+	// Every iteration doubles the number of correct bits
+	let mut inv = 1u64;
+	let mut i = 0;
+
+	while i < 6 {
+		inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+		i += 1;
+	}
+
+	inv.wrapping_neg()
+}
+
+/// Computes `R^2 = 2^512 (mod p)` by the modular doubling.
+const fn mont_r2(p: &Uint256) -> Uint256 {
+	let mut r = [1u64, 0, 0, 0];
+	let mut i = 0;
+
+	while i < 512 {
+		let (dbl, carry) = add_carry(&r, &r);
+		let (diff, borrow) = sub_borrow(&dbl, &p.0);
+		r = select(&dbl, &diff, carry == 0 && borrow == 1);
+		i += 1;
+	}
+
+	Uint256(r)
+}
+
+/// Montgomery multiplication: `a * b * R^(-1) (mod p)`.
+///
+/// Result is fully reduced if `a * b < p * R`.
+const fn mont_mul(a: &[u64; 4], b: &[u64; 4], p: &[u64; 4], inv: u64) -> [u64; 4] {
+	let mut t = [0u64; 6];
+	let mut i = 0;
+
+	while i < 4 {
+		// t = t + a * b_i
+		let mut carry = 0u128;
+		let mut j = 0;
+		while j < 4 {
+			let s = t[j] as u128 + a[j] as u128 * b[i] as u128 + carry;
+			t[j] = s as u64;
+			carry = s >> 64;
+			j += 1;
+		}
+
+		let s = t[4] as u128 + carry;
+		t[4] = s as u64;
+		t[5] = (s >> 64) as u64;
+
+		// t = (t + m * p) / 2^64, where `m` makes the lowest digit zero
+		let m = t[0].wrapping_mul(inv);
+		let mut carry = (t[0] as u128 + m as u128 * p[0] as u128) >> 64;
+		let mut j = 1;
+		while j < 4 {
+			let s = t[j] as u128 + m as u128 * p[j] as u128 + carry;
+			t[j - 1] = s as u64;
+			carry = s >> 64;
+			j += 1;
+		}
+
+		let s = t[4] as u128 + carry;
+		t[3] = s as u64;
+		t[4] = t[5] + (s >> 64) as u64;
+		i += 1;
+	}
+
+	// t < 2p, so the single subtraction is enough
+	let res = [t[0], t[1], t[2], t[3]];
+	let (diff, borrow) = sub_borrow(&res, p);
+	select(&res, &diff, t[4] == 0 && borrow == 1)
+}
+
+#[test]
+fn test_mont_inv() {
+	// secp256k1 `p` and `n`
+	assert_eq!(mont_inv(0xfffffffefffffc2f), 0xd838091dd2253531);
+	assert_eq!(mont_inv(0xbfd25e8cd0364141), 0x4b0dff665588b13f);
+}
+
+#[test]
+fn test_mont_r2() {
+	let p = Uint256([0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]);
+	assert_eq!(mont_r2(&p), Uint256([0x000007a2000e90a1, 0x0000000000000001, 0, 0]));
+
+	let n = Uint256([0xbfd25e8cd0364141, 0xbaaedce6af48a03b, 0xfffffffffffffffe, 0xffffffffffffffff]);
+	assert_eq!(mont_r2(&n), Uint256([0x896cf21467d7d140, 0x741496c20e7cf878, 0xe697f5e45bcd07c6, 0x9d671cd581c69bc5]));
+}
+
+#[test]
+fn test_mont_mul() {
+	let p = [0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff];
+	let inv = mont_inv(p[0]);
+	let r2 = mont_r2(&Uint256(p)).0;
+
+	// (p - 1) * R * R^(-1) = p - 1
+	let a = [0xfffffffefffffc2e, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff];
+	let a_mont = mont_mul(&a, &r2, &p, inv);
+	assert_eq!(mont_mul(&a_mont, &[1, 0, 0, 0], &p, inv), a);
+
+	// (p - 1)^2 = 1 (mod p)
+	let sq = mont_mul(&a_mont, &a_mont, &p, inv);
+	assert_eq!(mont_mul(&sq, &[1, 0, 0, 0], &p, inv), [1, 0, 0, 0]);
+}
@@ -0,0 +1,255 @@
+use super::Hasher;
+
+/// Initialization vector, the same as the initial state of SHA-512.
+const IV: [u64; 8] = [
+	0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+	0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179
+];
+
+/// Permutations of the message words, the round `i` uses `SIGMA[i % 10]`.
+const SIGMA: [[usize; 16]; 10] = [
+	[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+	[14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+	[11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+	[7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+	[9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+	[2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+	[12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+	[13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+	[6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+	[10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0]
+];
+
+/// Number of the rounds of the compression function.
+const ROUNDS: usize = 12;
+
+/// Size of the block in bytes.
+pub const BLOCK_SIZE: usize = 128;
+
+/// Maximum size of the digest and the key in bytes.
+pub const MAX_DIGEST_SIZE: usize = 64;
+
+/// Size of the personalization string in bytes.
+pub const PERSONAL_SIZE: usize = 16;
+
+/// BLAKE2b hash function (RFC 7693) with the configurable digest length, key and personalization.
+#[derive(Clone)]
+pub struct Blake2b {
+	/// Current hash value.
+	state: [u64; 8],
+
+	/// Buffered input, the last block is kept until the finalization since it's processed differently.
+	buffer: [u8; BLOCK_SIZE],
+
+	/// Number of the bytes in the buffer.
+	buffer_len: usize,
+
+	/// Number of the processed bytes.
+	len: u128,
+
+	/// Size of the digest in bytes.
+	digest_len: usize
+}
+
+impl Blake2b {
+	/// Creates the hasher with the digest of `digest_len` bytes, the key (empty for the unkeyed hashing)
+	/// and the personalization string (zeros if not used).
+	///
+	/// # Panics
+	///
+	/// Panics if `digest_len` isn't in the `[1; 64]` range or the key is longer than 64 bytes.
+	pub fn with_params(digest_len: usize, key: &[u8], personal: &[u8; PERSONAL_SIZE]) -> Self {
+		assert!((1..=MAX_DIGEST_SIZE).contains(&digest_len), "BLAKE2b digest length should be in the [1; 64] range.");
+		assert!(key.len() <= MAX_DIGEST_SIZE, "BLAKE2b key should be at most 64 bytes long.");
+
+		// Parameter block: digest length, key length, fanout = 1, depth = 1, the salt is zero
+		let mut state = IV;
+		state[0] ^= 0x01010000 ^ ((key.len() as u64) << 8) ^ digest_len as u64;
+		state[6] ^= read_u64(&personal[..8]);
+		state[7] ^= read_u64(&personal[8..]);
+
+		let mut hasher = Blake2b { state, buffer: [0; BLOCK_SIZE], buffer_len: 0, len: 0, digest_len };
+
+		// The key is padded to the full block, which is processed before the message
+		if !key.is_empty() {
+			let mut block = [0u8; BLOCK_SIZE];
+			block[..key.len()].copy_from_slice(key);
+			hasher.update(&block);
+		}
+
+		hasher
+	}
+
+	/// Returns the size of the digest in bytes.
+	pub fn digest_len(&self) -> usize { self.digest_len }
+
+	/// Processes the next part of the message.
+	pub fn update(&mut self, mut data: &[u8]) {
+		while !data.is_empty() {
+			// The full block is compressed only when more data arrives
+			if self.buffer_len == BLOCK_SIZE {
+				self.len += BLOCK_SIZE as u128;
+				let block = self.buffer;
+				compress(&mut self.state, &block, self.len, false);
+				self.buffer_len = 0;
+			}
+
+			let count = (BLOCK_SIZE - self.buffer_len).min(data.len());
+			self.buffer[self.buffer_len..self.buffer_len + count].copy_from_slice(&data[..count]);
+			self.buffer_len += count;
+			data = &data[count..];
+		}
+	}
+
+	/// Finishes the hashing and returns the digest of `digest_len` bytes.
+	pub fn finalize(self) -> Vec<u8> {
+		let mut res = vec![0u8; self.digest_len];
+		self.finalize_into(&mut res);
+		res
+	}
+
+	/// Finishes the hashing and writes the digest into `out`, which should be `digest_len` bytes long.
+	fn finalize_into(mut self, out: &mut [u8]) {
+		self.len += self.buffer_len as u128;
+		for byte in self.buffer[self.buffer_len..].iter_mut() {
+			*byte = 0;
+		}
+
+		let block = self.buffer;
+		compress(&mut self.state, &block, self.len, true);
+
+		let mut bytes = [0u8; MAX_DIGEST_SIZE];
+		for (chunk, word) in bytes.chunks_exact_mut(8).zip(self.state.iter()) {
+			chunk.copy_from_slice(&word.to_le_bytes());
+		}
+
+		out.copy_from_slice(&bytes[..self.digest_len]);
+	}
+}
+
+/// BLAKE2b with the 256-bit digest, without the key and the personalization.
+#[derive(Clone)]
+pub struct Blake2b256 {
+	inner: Blake2b
+}
+
+impl Hasher for Blake2b256 {
+	const BLOCK_SIZE: usize = BLOCK_SIZE;
+	const DIGEST_SIZE: usize = 32;
+	type Digest = [u8; 32];
+
+	fn new() -> Self {
+		Blake2b256 { inner: Blake2b::with_params(32, &[], &[0; PERSONAL_SIZE]) }
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		self.inner.update(data);
+	}
+
+	fn finalize(self) -> [u8; 32] {
+		let mut res = [0u8; 32];
+		self.inner.finalize_into(&mut res);
+		res
+	}
+}
+
+impl Default for Blake2b256 {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// BLAKE2b with the 512-bit digest, without the key and the personalization.
+#[derive(Clone)]
+pub struct Blake2b512 {
+	inner: Blake2b
+}
+
+impl Hasher for Blake2b512 {
+	const BLOCK_SIZE: usize = BLOCK_SIZE;
+	const DIGEST_SIZE: usize = MAX_DIGEST_SIZE;
+	type Digest = [u8; MAX_DIGEST_SIZE];
+
+	fn new() -> Self {
+		Blake2b512 { inner: Blake2b::with_params(MAX_DIGEST_SIZE, &[], &[0; PERSONAL_SIZE]) }
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		self.inner.update(data);
+	}
+
+	fn finalize(self) -> [u8; MAX_DIGEST_SIZE] {
+		let mut res = [0u8; MAX_DIGEST_SIZE];
+		self.inner.finalize_into(&mut res);
+		res
+	}
+}
+
+impl Default for Blake2b512 {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Returns BLAKE2b-256 digest of the data.
+pub fn blake2b256(data: &[u8]) -> [u8; 32] {
+	Blake2b256::digest(data)
+}
+
+/// Returns BLAKE2b-512 digest of the data.
+pub fn blake2b512(data: &[u8]) -> [u8; MAX_DIGEST_SIZE] {
+	Blake2b512::digest(data)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+	let mut word = [0u8; 8];
+	word.copy_from_slice(bytes);
+	u64::from_le_bytes(word)
+}
+
+/// The mixing function `G`, which mixes two message words into four state words.
+fn mix(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+	v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+	v[d] = (v[d] ^ v[a]).rotate_right(32);
+	v[c] = v[c].wrapping_add(v[d]);
+	v[b] = (v[b] ^ v[c]).rotate_right(24);
+	v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+	v[d] = (v[d] ^ v[a]).rotate_right(16);
+	v[c] = v[c].wrapping_add(v[d]);
+	v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+/// Processes one block of the message, `len` is the number of the message bytes including the block.
+fn compress(state: &mut [u64; 8], block: &[u8; BLOCK_SIZE], len: u128, last: bool) {
+	let mut m = [0u64; 16];
+	for (word, chunk) in m.iter_mut().zip(block.chunks_exact(8)) {
+		*word = read_u64(chunk);
+	}
+
+	let mut v = [0u64; 16];
+	v[..8].copy_from_slice(state);
+	v[8..].copy_from_slice(&IV);
+	v[12] ^= len as u64;
+	v[13] ^= (len >> 64) as u64;
+	if last {
+		v[14] = !v[14];
+	}
+
+	for i in 0..ROUNDS {
+		let s = &SIGMA[i % 10];
+
+		// Columns, then diagonals
+		mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+		mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+		mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+		mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+		mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+		mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+		mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+		mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+	}
+
+	for (i, s) in state.iter_mut().enumerate() {
+		*s ^= v[i] ^ v[i + 8];
+	}
+}
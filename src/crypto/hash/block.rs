@@ -0,0 +1,74 @@
+/// Input buffer of the Merkle-Damgård hash functions with the blocks of `N` bytes.
+///
+/// Collects the message into the full blocks, passes them to the compression function
+/// and pads the last block with `0x80`, zeros and the encoded message length.
+#[derive(Clone)]
+pub(crate) struct BlockBuffer<const N: usize> {
+	/// Buffered input that doesn't fill the whole block.
+	buffer: [u8; N],
+
+	/// Number of the bytes in the buffer.
+	buffer_len: usize,
+
+	/// Number of the processed bytes.
+	len: u128
+}
+
+impl<const N: usize> BlockBuffer<N> {
+	/// Creates the buffer of the empty message.
+	pub fn new() -> Self {
+		BlockBuffer { buffer: [0; N], buffer_len: 0, len: 0 }
+	}
+
+	/// Returns the length of the message in bits, modulo `2^128`.
+	pub fn bit_len(&self) -> u128 {
+		self.len.wrapping_mul(8)
+	}
+
+	/// Processes the next part of the message, `compress` is called for every full block.
+	pub fn update<F: FnMut(&[u8])>(&mut self, mut data: &[u8], mut compress: F) {
+		self.len = self.len.wrapping_add(data.len() as u128);
+
+		// Fill the buffered block first
+		if self.buffer_len > 0 {
+			let count = (N - self.buffer_len).min(data.len());
+			self.buffer[self.buffer_len..self.buffer_len + count].copy_from_slice(&data[..count]);
+			self.buffer_len += count;
+			data = &data[count..];
+
+			if self.buffer_len < N {
+				return;
+			}
+
+			compress(&self.buffer);
+			self.buffer_len = 0;
+		}
+
+		let mut blocks = data.chunks_exact(N);
+		for block in &mut blocks {
+			compress(block);
+		}
+
+		let rest = blocks.remainder();
+		self.buffer[..rest.len()].copy_from_slice(rest);
+		self.buffer_len = rest.len();
+	}
+
+	/// Pads the message with `0x80`, zeros and the encoded length, so the total length is a multiple
+	/// of the block size, and passes the last one or two blocks to `compress`.
+	pub fn finalize<F: FnMut(&[u8])>(mut self, length: &[u8], mut compress: F) {
+		self.buffer[self.buffer_len] = 0x80;
+		for byte in self.buffer[self.buffer_len + 1..].iter_mut() {
+			*byte = 0;
+		}
+
+		// The length doesn't fit into the rest of the block, so one more block is added
+		if self.buffer_len + 1 > N - length.len() {
+			compress(&self.buffer);
+			self.buffer = [0; N];
+		}
+
+		self.buffer[N - length.len()..].copy_from_slice(length);
+		compress(&self.buffer);
+	}
+}
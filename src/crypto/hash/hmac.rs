@@ -0,0 +1,65 @@
+use super::{Hasher, Sha256, Sha512};
+
+/// HMAC over the hash function `H` (RFC 2104).
+#[derive(Clone)]
+pub struct Hmac<H: Hasher> {
+	/// Hasher of the inner message: `H((K ^ ipad) || message)`.
+	inner: H,
+
+	/// Hasher of the outer message: `H((K ^ opad) || inner)`.
+	outer: H
+}
+
+/// HMAC over SHA-256.
+pub type HmacSha256 = Hmac<Sha256>;
+
+/// HMAC over SHA-512.
+pub type HmacSha512 = Hmac<Sha512>;
+
+impl<H: Hasher> Hmac<H> {
+	/// Creates HMAC with the key of any length.
+	pub fn new(key: &[u8]) -> Self {
+		// The long keys are hashed, the short keys are padded with zeros
+		let mut block = vec![0u8; H::BLOCK_SIZE];
+		if key.len() > H::BLOCK_SIZE {
+			block[..H::DIGEST_SIZE].copy_from_slice(H::digest(key).as_ref());
+		} else {
+			block[..key.len()].copy_from_slice(key);
+		}
+
+		let (mut inner, mut outer) = (H::new(), H::new());
+		inner.update(&block.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>());
+		outer.update(&block.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>());
+
+		Hmac { inner, outer }
+	}
+
+	/// Processes the next part of the message.
+	pub fn update(&mut self, data: &[u8]) {
+		self.inner.update(data);
+	}
+
+	/// Finishes the computation and returns the authentication code.
+	pub fn finalize(self) -> H::Digest {
+		let mut outer = self.outer;
+		outer.update(self.inner.finalize().as_ref());
+		outer.finalize()
+	}
+}
+
+/// Returns HMAC of the data over the hash function `H`.
+pub fn hmac<H: Hasher>(key: &[u8], data: &[u8]) -> H::Digest {
+	let mut hmac = Hmac::<H>::new(key);
+	hmac.update(data);
+	hmac.finalize()
+}
+
+/// Returns HMAC-SHA256 of the data.
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+	hmac::<Sha256>(key, data)
+}
+
+/// Returns HMAC-SHA512 of the data.
+pub fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+	hmac::<Sha512>(key, data)
+}
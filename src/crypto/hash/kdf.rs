@@ -0,0 +1,89 @@
+use super::{Hasher, Hmac, hmac};
+
+/// HKDF-Extract (RFC 5869, section 2.2): derives the pseudorandom key from the input key material.
+///
+/// The empty salt is replaced by the zeros of the digest length.
+pub fn hkdf_extract<H: Hasher>(salt: &[u8], ikm: &[u8]) -> H::Digest {
+	if salt.is_empty() {
+		hmac::<H>(&vec![0u8; H::DIGEST_SIZE], ikm)
+	} else {
+		hmac::<H>(salt, ikm)
+	}
+}
+
+/// HKDF-Expand (RFC 5869, section 2.3): derives `len` bytes of the output key material
+/// from the pseudorandom key and the context `info`.
+///
+/// Returns `None` if `len` is greater than `255 * H::DIGEST_SIZE`.
+pub fn hkdf_expand<H: Hasher>(prk: &[u8], info: &[u8], len: usize) -> Option<Vec<u8>> {
+	if len > 255 * H::DIGEST_SIZE {
+		return None;
+	}
+
+	// T(i) = HMAC(PRK, T(i - 1) || info || i), T(0) is empty
+	let mut okm = Vec::with_capacity(len);
+	let mut t = Vec::new();
+	let mut i = 1u8;
+
+	while okm.len() < len {
+		let mut hmac = Hmac::<H>::new(prk);
+		hmac.update(&t);
+		hmac.update(info);
+		hmac.update(&[i]);
+		t = hmac.finalize().as_ref().to_vec();
+
+		let count = (len - okm.len()).min(t.len());
+		okm.extend_from_slice(&t[..count]);
+		i = i.wrapping_add(1);
+	}
+
+	Some(okm)
+}
+
+/// HKDF (RFC 5869): extracts the pseudorandom key and expands it into `len` bytes.
+///
+/// Returns `None` if `len` is greater than `255 * H::DIGEST_SIZE`.
+pub fn hkdf<H: Hasher>(salt: &[u8], ikm: &[u8], info: &[u8], len: usize) -> Option<Vec<u8>> {
+	hkdf_expand::<H>(hkdf_extract::<H>(salt, ikm).as_ref(), info, len)
+}
+
+/// PBKDF2 with HMAC over `H` (RFC 8018, section 5.2): derives `len` bytes from the password and the salt.
+///
+/// The number of the iterations should be as large as acceptable, it slows down the password guessing.
+///
+/// # Panics
+///
+/// Panics if `iterations` is zero.
+pub fn pbkdf2<H: Hasher>(password: &[u8], salt: &[u8], iterations: u32, len: usize) -> Vec<u8> {
+	assert!(iterations > 0, "PBKDF2 requires at least one iteration.");
+
+	// The key is the same for every HMAC, so its padded blocks are processed once
+	let prf = Hmac::<H>::new(password);
+	let mut res = Vec::with_capacity(len);
+	let mut block = 1u32;
+
+	while res.len() < len {
+		// U_1 = PRF(P, S || INT(i)), U_j = PRF(P, U_{j - 1}), T_i = U_1 ^ ... ^ U_c
+		let mut hmac = prf.clone();
+		hmac.update(salt);
+		hmac.update(&block.to_be_bytes());
+		let mut u = hmac.finalize();
+		let mut t = u.as_ref().to_vec();
+
+		for _ in 1..iterations {
+			let mut hmac = prf.clone();
+			hmac.update(u.as_ref());
+			u = hmac.finalize();
+
+			for (t, u) in t.iter_mut().zip(u.as_ref().iter()) {
+				*t ^= u;
+			}
+		}
+
+		let count = (len - res.len()).min(t.len());
+		res.extend_from_slice(&t[..count]);
+		block += 1;
+	}
+
+	res
+}
@@ -0,0 +1,198 @@
+use super::Hasher;
+
+/// Round constants of the Keccak-f[1600] permutation.
+const RC: [u64; 24] = [
+	0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+	0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+	0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+	0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+	0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+	0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008
+];
+
+/// Rotation amounts of the lanes in the order of the `PI` permutation.
+const ROTATION: [u32; 24] = [1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44];
+
+/// The `pi` step: the lane positions visited starting from the lane 1.
+const PI: [usize; 24] = [10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1];
+
+/// Rate of the sponge in bytes for the 256-bit output: `(1600 - 2 * 256) / 8`.
+pub const RATE: usize = 136;
+
+/// Size of the digest in bytes.
+pub const DIGEST_SIZE: usize = 32;
+
+/// Keccak sponge with the 256-bit capacity and the given domain padding.
+#[derive(Clone)]
+struct Sponge {
+	/// The state of 25 lanes.
+	state: [u64; 25],
+
+	/// Buffered input that doesn't fill the whole block.
+	buffer: [u8; RATE],
+
+	/// Number of the bytes in the buffer.
+	buffer_len: usize,
+
+	/// The first byte of the padding: `0x01` for the original Keccak, `0x06` for SHA-3.
+	padding: u8
+}
+
+impl Sponge {
+	fn new(padding: u8) -> Self {
+		Sponge { state: [0; 25], buffer: [0; RATE], buffer_len: 0, padding }
+	}
+
+	fn update(&mut self, mut data: &[u8]) {
+		while !data.is_empty() {
+			let count = (RATE - self.buffer_len).min(data.len());
+			self.buffer[self.buffer_len..self.buffer_len + count].copy_from_slice(&data[..count]);
+			self.buffer_len += count;
+			data = &data[count..];
+
+			if self.buffer_len == RATE {
+				self.absorb_buffer();
+			}
+		}
+	}
+
+	fn finalize(mut self) -> [u8; DIGEST_SIZE] {
+		// Padding: the domain bits, zeros and the final bit, it fits into one byte if one byte is left
+		for byte in self.buffer[self.buffer_len..].iter_mut() {
+			*byte = 0;
+		}
+		self.buffer[self.buffer_len] ^= self.padding;
+		self.buffer[RATE - 1] ^= 0x80;
+		self.absorb_buffer();
+
+		let mut res = [0u8; DIGEST_SIZE];
+		for (chunk, lane) in res.chunks_exact_mut(8).zip(self.state.iter()) {
+			chunk.copy_from_slice(&lane.to_le_bytes());
+		}
+
+		res
+	}
+
+	/// Mixes the full buffer into the state and permutes it.
+	fn absorb_buffer(&mut self) {
+		for (lane, chunk) in self.state.iter_mut().zip(self.buffer.chunks_exact(8)) {
+			let mut word = [0u8; 8];
+			word.copy_from_slice(chunk);
+			*lane ^= u64::from_le_bytes(word);
+		}
+
+		keccak_f(&mut self.state);
+		self.buffer_len = 0;
+	}
+}
+
+/// Keccak-256, the original Keccak submission with the 256-bit output (used by Ethereum).
+///
+/// Differs from SHA3-256 only by the padding.
+#[derive(Clone)]
+pub struct Keccak256 {
+	sponge: Sponge
+}
+
+impl Hasher for Keccak256 {
+	const BLOCK_SIZE: usize = RATE;
+	const DIGEST_SIZE: usize = DIGEST_SIZE;
+	type Digest = [u8; DIGEST_SIZE];
+
+	fn new() -> Self {
+		Keccak256 { sponge: Sponge::new(0x01) }
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		self.sponge.update(data);
+	}
+
+	fn finalize(self) -> [u8; DIGEST_SIZE] {
+		self.sponge.finalize()
+	}
+}
+
+impl Default for Keccak256 {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// SHA3-256 hash function (FIPS 202).
+#[derive(Clone)]
+pub struct Sha3_256 {
+	sponge: Sponge
+}
+
+impl Hasher for Sha3_256 {
+	const BLOCK_SIZE: usize = RATE;
+	const DIGEST_SIZE: usize = DIGEST_SIZE;
+	type Digest = [u8; DIGEST_SIZE];
+
+	fn new() -> Self {
+		Sha3_256 { sponge: Sponge::new(0x06) }
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		self.sponge.update(data);
+	}
+
+	fn finalize(self) -> [u8; DIGEST_SIZE] {
+		self.sponge.finalize()
+	}
+}
+
+impl Default for Sha3_256 {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Returns Keccak-256 digest of the data.
+pub fn keccak256(data: &[u8]) -> [u8; DIGEST_SIZE] {
+	Keccak256::digest(data)
+}
+
+/// Returns SHA3-256 digest of the data.
+pub fn sha3_256(data: &[u8]) -> [u8; DIGEST_SIZE] {
+	Sha3_256::digest(data)
+}
+
+/// The Keccak-f[1600] permutation.
+fn keccak_f(state: &mut [u64; 25]) {
+	for rc in RC.iter() {
+		// Theta: every lane is mixed with the parities of two neighbour columns
+		let mut parity = [0u64; 5];
+		for (x, p) in parity.iter_mut().enumerate() {
+			*p = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+		}
+
+		for x in 0..5 {
+			let t = parity[(x + 4) % 5] ^ parity[(x + 1) % 5].rotate_left(1);
+			for y in 0..5 {
+				state[5 * y + x] ^= t;
+			}
+		}
+
+		// Rho and pi: the lanes are rotated and moved
+		let mut last = state[1];
+		for (&pos, &rot) in PI.iter().zip(ROTATION.iter()) {
+			let tmp = state[pos];
+			state[pos] = last.rotate_left(rot);
+			last = tmp;
+		}
+
+		// Chi: the nonlinear mixing of the rows
+		for y in 0..5 {
+			let mut row = [0u64; 5];
+			row.copy_from_slice(&state[5 * y..5 * y + 5]);
+
+			for x in 0..5 {
+				state[5 * y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+			}
+		}
+
+		// Iota
+		state[0] ^= rc;
+	}
+}
@@ -0,0 +1,56 @@
+mod block;
+mod sha256;
+mod sha512;
+mod ripemd160;
+mod keccak;
+mod blake2b;
+mod hmac;
+mod kdf;
+mod scrypt;
+
+pub use self::sha256::{Sha256, Sha256d, sha256, sha256d, sha256d_uint};
+pub use self::sha512::{Sha512, sha512};
+pub use self::ripemd160::{Ripemd160, Hash160, ripemd160, hash160};
+pub use self::keccak::{Keccak256, Sha3_256, keccak256, sha3_256};
+pub use self::blake2b::{Blake2b, Blake2b256, Blake2b512, blake2b256, blake2b512};
+pub use self::hmac::{Hmac, HmacSha256, HmacSha512, hmac, hmac_sha256, hmac_sha512};
+pub use self::kdf::{hkdf, hkdf_extract, hkdf_expand, pbkdf2};
+pub use self::scrypt::scrypt;
+
+use utils::bignum::Uint256;
+use std::fmt;
+
+/// Hash function with the streaming interface.
+pub trait Hasher: Clone {
+	/// Size of the block in bytes.
+	const BLOCK_SIZE: usize;
+
+	/// Size of the digest in bytes.
+	const DIGEST_SIZE: usize;
+
+	/// The digest, the byte array of `DIGEST_SIZE` length.
+	type Digest: AsRef<[u8]> + Copy + Eq + fmt::Debug;
+
+	/// Creates the hasher of the empty message.
+	fn new() -> Self;
+
+	/// Processes the next part of the message.
+	fn update(&mut self, data: &[u8]);
+
+	/// Finishes the hashing and returns the digest.
+	fn finalize(self) -> Self::Digest;
+
+	/// Returns digest of the data.
+	fn digest(data: &[u8]) -> Self::Digest {
+		let mut hasher = Self::new();
+		hasher.update(data);
+		hasher.finalize()
+	}
+
+	/// Returns 256-bit digest of the data as the big-endian number, e.g. to use it as the identifier.
+	///
+	/// Note that Bitcoin treats the double SHA-256 digests as the little-endian numbers (see `sha256d_uint`).
+	fn digest_uint(data: &[u8]) -> Uint256 where Self: Hasher<Digest = [u8; 32]> {
+		Uint256::from_be_bytes(&Self::digest(data))
+	}
+}
@@ -0,0 +1,183 @@
+use super::Hasher;
+use super::block::BlockBuffer;
+use super::sha256::{Sha256, sha256};
+
+/// Initial state.
+const H0: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// Indices of the message words of the left line.
+const R_LEFT: [usize; 80] = [
+	0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+	7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+	3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+	1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+	4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13
+];
+
+/// Indices of the message words of the right line.
+const R_RIGHT: [usize; 80] = [
+	5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+	6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+	15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+	8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+	12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11
+];
+
+/// Rotation amounts of the left line.
+const S_LEFT: [u32; 80] = [
+	11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+	7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+	11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+	11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+	9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6
+];
+
+/// Rotation amounts of the right line.
+const S_RIGHT: [u32; 80] = [
+	8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+	9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+	9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+	15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+	8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11
+];
+
+/// Round constants of the left line.
+const K_LEFT: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+
+/// Round constants of the right line.
+const K_RIGHT: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// Size of the block in bytes.
+pub const BLOCK_SIZE: usize = 64;
+
+/// Size of the digest in bytes.
+pub const DIGEST_SIZE: usize = 20;
+
+/// RIPEMD-160 hash function.
+#[derive(Clone)]
+pub struct Ripemd160 {
+	/// Current hash value.
+	state: [u32; 5],
+
+	/// Buffered input and the message length.
+	buffer: BlockBuffer<BLOCK_SIZE>
+}
+
+impl Hasher for Ripemd160 {
+	const BLOCK_SIZE: usize = BLOCK_SIZE;
+	const DIGEST_SIZE: usize = DIGEST_SIZE;
+	type Digest = [u8; DIGEST_SIZE];
+
+	fn new() -> Self {
+		Ripemd160 { state: H0, buffer: BlockBuffer::new() }
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		let state = &mut self.state;
+		self.buffer.update(data, |block| compress(state, block));
+	}
+
+	fn finalize(mut self) -> [u8; DIGEST_SIZE] {
+		// The same padding as SHA-256, but the length is little-endian
+		let length = (self.buffer.bit_len() as u64).to_le_bytes();
+		let state = &mut self.state;
+		self.buffer.finalize(&length, |block| compress(state, block));
+
+		let mut res = [0u8; DIGEST_SIZE];
+		for (chunk, word) in res.chunks_exact_mut(4).zip(self.state.iter()) {
+			chunk.copy_from_slice(&word.to_le_bytes());
+		}
+
+		res
+	}
+}
+
+impl Default for Ripemd160 {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Returns RIPEMD-160 digest of the data.
+pub fn ripemd160(data: &[u8]) -> [u8; DIGEST_SIZE] {
+	Ripemd160::digest(data)
+}
+
+/// Hash160: `ripemd160(sha256(data))`, used for the addresses.
+#[derive(Clone, Default)]
+pub struct Hash160 {
+	inner: Sha256
+}
+
+impl Hasher for Hash160 {
+	const BLOCK_SIZE: usize = BLOCK_SIZE;
+	const DIGEST_SIZE: usize = DIGEST_SIZE;
+	type Digest = [u8; DIGEST_SIZE];
+
+	fn new() -> Self {
+		Hash160 { inner: Sha256::new() }
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		self.inner.update(data);
+	}
+
+	fn finalize(self) -> [u8; DIGEST_SIZE] {
+		ripemd160(&self.inner.finalize())
+	}
+}
+
+/// Returns Hash160 digest of the data.
+pub fn hash160(data: &[u8]) -> [u8; DIGEST_SIZE] {
+	ripemd160(&sha256(data))
+}
+
+/// The nonlinear function of the round `j / 16`.
+fn f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+	match round {
+		0 => x ^ y ^ z,
+		1 => (x & y) | (!x & z),
+		2 => (x | !y) ^ z,
+		3 => (x & z) | (y & !z),
+		_ => x ^ (y | !z)
+	}
+}
+
+/// Processes one block of the message.
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+	let mut x = [0u32; 16];
+	for (i, chunk) in block.chunks_exact(4).enumerate() {
+		x[i] = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+	}
+
+	let [mut al, mut bl, mut cl, mut dl, mut el] = *state;
+	let [mut ar, mut br, mut cr, mut dr, mut er] = *state;
+
+	for j in 0..80 {
+		let round = j / 16;
+
+		// The left line uses the functions in the direct order, the right line in the reverse order
+		let t = al.wrapping_add(f(round, bl, cl, dl)).wrapping_add(x[R_LEFT[j]]).wrapping_add(K_LEFT[round])
+			.rotate_left(S_LEFT[j]).wrapping_add(el);
+		al = el;
+		el = dl;
+		dl = cl.rotate_left(10);
+		cl = bl;
+		bl = t;
+
+		let t = ar.wrapping_add(f(4 - round, br, cr, dr)).wrapping_add(x[R_RIGHT[j]]).wrapping_add(K_RIGHT[round])
+			.rotate_left(S_RIGHT[j]).wrapping_add(er);
+		ar = er;
+		er = dr;
+		dr = cr.rotate_left(10);
+		cr = br;
+		br = t;
+	}
+
+	let t = state[1].wrapping_add(cl).wrapping_add(dr);
+	state[1] = state[2].wrapping_add(dl).wrapping_add(er);
+	state[2] = state[3].wrapping_add(el).wrapping_add(ar);
+	state[3] = state[4].wrapping_add(al).wrapping_add(br);
+	state[4] = state[0].wrapping_add(bl).wrapping_add(cr);
+	state[0] = t;
+}
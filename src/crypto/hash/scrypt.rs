@@ -0,0 +1,105 @@
+use super::{Sha256, pbkdf2};
+
+/// Size of the Salsa20 block in bytes.
+const SALSA_BLOCK_SIZE: usize = 64;
+
+/// scrypt key derivation function (RFC 7914): derives `len` bytes from the password and the salt.
+///
+/// The cost `n` is the number of the blocks of `128 * r` bytes kept in the memory, `p` is the number
+/// of the independent mixings. The memory usage is `128 * n * r` bytes.
+///
+/// # Panics
+///
+/// Panics if `n` isn't the power of two greater than one, `r` or `p` is zero or `p * r >= 2^30`.
+pub fn scrypt(password: &[u8], salt: &[u8], n: usize, r: usize, p: usize, len: usize) -> Vec<u8> {
+	assert!(n > 1 && n.is_power_of_two(), "scrypt cost should be the power of two greater than one.");
+	assert!(r > 0 && p > 0 && (p as u64) * (r as u64) < 1 << 30, "scrypt block size or parallelization is invalid.");
+
+	let block_len = 128 * r;
+	let mut b = pbkdf2::<Sha256>(password, salt, 1, p * block_len);
+
+	for chunk in b.chunks_exact_mut(block_len) {
+		ro_mix(chunk, n);
+	}
+
+	pbkdf2::<Sha256>(password, &b, 1, len)
+}
+
+/// The sequential memory-hard function `ROMix`, mixes the block of `128 * r` bytes in place.
+fn ro_mix(block: &mut [u8], n: usize) {
+	let words = block.len() / 4;
+	let mut x: Vec<u32> = block.chunks_exact(4).map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect();
+	let mut v = vec![0u32; n * words];
+	let mut tmp = vec![0u32; words];
+
+	// V_i = X, X = BlockMix(X)
+	for i in 0..n {
+		v[i * words..(i + 1) * words].copy_from_slice(&x);
+		block_mix(&x, &mut tmp);
+		x.copy_from_slice(&tmp);
+	}
+
+	// j = Integerify(X) mod N, X = BlockMix(X ^ V_j)
+	for _ in 0..n {
+		let last = words - SALSA_BLOCK_SIZE / 4;
+		let j = (x[last] as u64 | (x[last + 1] as u64) << 32) as usize & (n - 1);
+
+		for (x, v) in x.iter_mut().zip(v[j * words..(j + 1) * words].iter()) {
+			*x ^= v;
+		}
+
+		block_mix(&x, &mut tmp);
+		x.copy_from_slice(&tmp);
+	}
+
+	for (chunk, word) in block.chunks_exact_mut(4).zip(x.iter()) {
+		chunk.copy_from_slice(&word.to_le_bytes());
+	}
+}
+
+/// The `BlockMix` function over Salsa20/8: the even blocks of the result go first, then the odd ones.
+fn block_mix(input: &[u32], output: &mut [u32]) {
+	let blocks = input.len() / 16;
+	let mut x = [0u32; 16];
+	x.copy_from_slice(&input[(blocks - 1) * 16..]);
+
+	for (i, block) in input.chunks_exact(16).enumerate() {
+		for (x, b) in x.iter_mut().zip(block.iter()) {
+			*x ^= b;
+		}
+		salsa20_8(&mut x);
+
+		let pos = (i / 2 + (i % 2) * blocks / 2) * 16;
+		output[pos..pos + 16].copy_from_slice(&x);
+	}
+}
+
+/// The Salsa20/8 core: 8 rounds of Salsa20 with the feedforward of the input.
+fn salsa20_8(block: &mut [u32; 16]) {
+	let mut x = *block;
+
+	for _ in 0..4 {
+		// Columns
+		quarter_round(&mut x, 0, 4, 8, 12);
+		quarter_round(&mut x, 5, 9, 13, 1);
+		quarter_round(&mut x, 10, 14, 2, 6);
+		quarter_round(&mut x, 15, 3, 7, 11);
+
+		// Rows
+		quarter_round(&mut x, 0, 1, 2, 3);
+		quarter_round(&mut x, 5, 6, 7, 4);
+		quarter_round(&mut x, 10, 11, 8, 9);
+		quarter_round(&mut x, 15, 12, 13, 14);
+	}
+
+	for (b, x) in block.iter_mut().zip(x.iter()) {
+		*b = b.wrapping_add(*x);
+	}
+}
+
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+	x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+	x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+	x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+	x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+}
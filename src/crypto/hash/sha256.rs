@@ -0,0 +1,151 @@
+use utils::bignum::Uint256;
+use super::Hasher;
+use super::block::BlockBuffer;
+
+/// Round constants: the first 32 bits of the fractional parts of the cube roots of the first 64 primes.
+const K: [u32; 64] = [
+	0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+	0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+	0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+	0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+	0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+	0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+	0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+	0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2
+];
+
+/// Initial state: the first 32 bits of the fractional parts of the square roots of the first 8 primes.
+const H0: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+
+/// Size of the block in bytes.
+pub const BLOCK_SIZE: usize = 64;
+
+/// Size of the digest in bytes.
+pub const DIGEST_SIZE: usize = 32;
+
+/// SHA-256 hash function (FIPS 180-4).
+#[derive(Clone)]
+pub struct Sha256 {
+	/// Current hash value.
+	state: [u32; 8],
+
+	/// Buffered input and the message length.
+	buffer: BlockBuffer<BLOCK_SIZE>
+}
+
+impl Hasher for Sha256 {
+	const BLOCK_SIZE: usize = BLOCK_SIZE;
+	const DIGEST_SIZE: usize = DIGEST_SIZE;
+	type Digest = [u8; DIGEST_SIZE];
+
+	fn new() -> Self {
+		Sha256 { state: H0, buffer: BlockBuffer::new() }
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		let state = &mut self.state;
+		self.buffer.update(data, |block| compress(state, block));
+	}
+
+	fn finalize(mut self) -> [u8; DIGEST_SIZE] {
+		// The message length in bits is big-endian
+		let length = (self.buffer.bit_len() as u64).to_be_bytes();
+		let state = &mut self.state;
+		self.buffer.finalize(&length, |block| compress(state, block));
+
+		let mut res = [0u8; DIGEST_SIZE];
+		for (chunk, word) in res.chunks_exact_mut(4).zip(self.state.iter()) {
+			chunk.copy_from_slice(&word.to_be_bytes());
+		}
+
+		res
+	}
+}
+
+impl Default for Sha256 {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Returns SHA-256 digest of the data.
+pub fn sha256(data: &[u8]) -> [u8; DIGEST_SIZE] {
+	Sha256::digest(data)
+}
+
+/// Double SHA-256: `sha256(sha256(data))`, used for the block and transaction IDs.
+#[derive(Clone, Default)]
+pub struct Sha256d {
+	inner: Sha256
+}
+
+impl Hasher for Sha256d {
+	const BLOCK_SIZE: usize = BLOCK_SIZE;
+	const DIGEST_SIZE: usize = DIGEST_SIZE;
+	type Digest = [u8; DIGEST_SIZE];
+
+	fn new() -> Self {
+		Sha256d { inner: Sha256::new() }
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		self.inner.update(data);
+	}
+
+	fn finalize(self) -> [u8; DIGEST_SIZE] {
+		sha256(&self.inner.finalize())
+	}
+}
+
+/// Returns double SHA-256 digest of the data.
+pub fn sha256d(data: &[u8]) -> [u8; DIGEST_SIZE] {
+	Sha256d::digest(data)
+}
+
+/// Returns double SHA-256 digest of the data as the little-endian number.
+///
+/// Bitcoin compares the block hashes with the target as such numbers and displays them in hex
+/// (e.g. the genesis block hash `000000000019d6...`), which is the digest in the reversed byte order.
+pub fn sha256d_uint(data: &[u8]) -> Uint256 {
+	let mut digest = sha256d(data);
+	digest.reverse();
+	Uint256::from_be_bytes(&digest)
+}
+
+/// Processes one block of the message.
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+	let mut w = [0u32; 64];
+	for (i, chunk) in block.chunks_exact(4).enumerate() {
+		w[i] = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+	}
+
+	for i in 16..64 {
+		let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+		let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+		w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+	}
+
+	let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+	for i in 0..64 {
+		let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+		let ch = (e & f) ^ (!e & g);
+		let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+		let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+		let maj = (a & b) ^ (a & c) ^ (b & c);
+		let t2 = s0.wrapping_add(maj);
+
+		h = g;
+		g = f;
+		f = e;
+		e = d.wrapping_add(t1);
+		d = c;
+		c = b;
+		b = a;
+		a = t1.wrapping_add(t2);
+	}
+
+	for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+		*s = s.wrapping_add(*v);
+	}
+}
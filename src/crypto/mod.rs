@@ -1 +1,7 @@
-pub mod ecdsa;
\ No newline at end of file
+pub mod field;
+pub mod ecdsa;
+pub mod eddsa;
+pub mod schnorr;
+pub mod ecdh;
+pub mod hash;
+pub mod bip32;
\ No newline at end of file
@@ -0,0 +1,187 @@
+use utils::bignum::{Uint256, Zero, One};
+use crypto::field::PrimeField;
+use crypto::ecdsa::{SECP256K1, ECPoint, PrivateKey, PointError, SignatureError, FieldElement, Scalar};
+use crypto::hash::{Hasher, Sha256, sha256};
+
+const AUX_TAG: &str = "BIP0340/aux";
+const NONCE_TAG: &str = "BIP0340/nonce";
+const CHALLENGE_TAG: &str = "BIP0340/challenge";
+const BATCH_TAG: &str = "BIP0340/batch";
+
+/// Computes the BIP340 tagged hash: `sha256(sha256(tag) || sha256(tag) || data)`.
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+	let mut hasher = tagged_hasher(tag);
+	hasher.update(data);
+	hasher.finalize()
+}
+
+/// Returns the hasher with the tag prefix already processed.
+fn tagged_hasher(tag: &str) -> Sha256 {
+	let tag_hash = sha256(tag.as_bytes());
+	let mut hasher = Sha256::new();
+	hasher.update(&tag_hash);
+	hasher.update(&tag_hash);
+	hasher
+}
+
+/// BIP340 public key: the point of the secp256k1 curve with the even `y`, encoded by its `x` coordinate.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct XOnlyPublicKey {
+	/// The point with the even `y`.
+	point: ECPoint
+}
+
+impl XOnlyPublicKey {
+	/// Decodes the key from the `x` coordinate, checking that it's a valid point of the curve.
+	pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, PointError> {
+		let x = FieldElement::from_bytes(bytes).ok_or(PointError::CoordinateOutOfRange)?;
+		Ok(XOnlyPublicKey { point: SECP256K1.lift_x(&x, false)? })
+	}
+
+	/// Returns the key of the private key, the point `dG` is negated if its `y` is odd.
+	pub fn from_private_key(key: &PrivateKey) -> Self {
+		let point = SECP256K1.mul_generator(key.scalar());
+		let point = if is_odd(point.y()) { -point } else { point };
+
+		XOnlyPublicKey { point }
+	}
+
+	/// Encodes the key as the `x` coordinate.
+	pub fn to_bytes(&self) -> [u8; 32] {
+		self.point.x().to_bytes()
+	}
+
+	/// Returns the point of the key, its `y` is even.
+	pub fn point(&self) -> &ECPoint { &self.point }
+}
+
+/// BIP340 signature: the `x` coordinate of the nonce point `R` and the scalar `s`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SchnorrSignature {
+	r: FieldElement,
+	s: Scalar
+}
+
+impl SchnorrSignature {
+	/// Decodes the signature from `r || s`, checking that `r` is less than the field size
+	/// and `s` is less than the order.
+	pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self, SignatureError> {
+		let mut buf = [0u8; 32];
+
+		buf.copy_from_slice(&bytes[..32]);
+		let r = FieldElement::from_bytes(&buf).ok_or(SignatureError::ComponentOutOfRange)?;
+
+		buf.copy_from_slice(&bytes[32..]);
+		let s = Scalar::from_bytes(&buf).ok_or(SignatureError::ComponentOutOfRange)?;
+
+		Ok(SchnorrSignature { r, s })
+	}
+
+	/// Encodes the signature as `r || s`.
+	pub fn to_bytes(&self) -> [u8; 64] {
+		let mut res = [0u8; 64];
+		res[..32].copy_from_slice(&self.r.to_bytes());
+		res[32..].copy_from_slice(&self.s.to_bytes());
+		res
+	}
+
+	pub fn r(&self) -> &FieldElement { &self.r }
+
+	pub fn s(&self) -> &Scalar { &self.s }
+}
+
+/// Signs the message by BIP340.
+///
+/// The auxiliary randomness is mixed into the nonce to protect from the side-channel attacks,
+/// the signature is still secure if it's constant (e.g. zero), but it should be fresh randomness if available.
+pub fn schnorr_sign(key: &PrivateKey, msg: &[u8], aux_rand: &[u8; 32]) -> SchnorrSignature {
+	let point = SECP256K1.mul_generator(key.scalar());
+	let d = Scalar::conditional_select(key.scalar(), &-*key.scalar(), is_odd(point.y()));
+	let p = point.x().to_bytes();
+
+	// t = d xor hash_aux(aux_rand)
+	let mut t = tagged_hash(AUX_TAG, aux_rand);
+	for (t, d) in t.iter_mut().zip(d.to_bytes().iter()) {
+		*t ^= d;
+	}
+
+	let mut hasher = tagged_hasher(NONCE_TAG);
+	hasher.update(&t);
+	hasher.update(&p);
+	hasher.update(msg);
+	let k = Scalar::reduce(&Uint256::from_be_bytes(&hasher.finalize()));
+	assert!(!k.is_zero(), "The nonce is zero, which requires the preimage of SHA-256.");
+
+	// The nonce is negated to get R with the even y
+	let r = SECP256K1.mul_generator(&k);
+	let k = Scalar::conditional_select(&k, &-k, is_odd(r.y()));
+	let e = challenge(r.x(), &p, msg);
+
+	SchnorrSignature { r: *r.x(), s: k + e * d }
+}
+
+/// Verifies the BIP340 signature of the message.
+pub fn schnorr_verify(key: &XOnlyPublicKey, msg: &[u8], sig: &SchnorrSignature) -> bool {
+	let e = challenge(&sig.r, &key.to_bytes(), msg);
+
+	// R = sG - eP
+	let r = SECP256K1.double_mul(&sig.s, &SECP256K1.gen_point(), &-e, &key.point);
+	!r.is_identity() && !is_odd(r.y()) && *r.x() == sig.r
+}
+
+/// Verifies the BIP340 signatures of the messages at once, returns `true` only if all of them are valid.
+///
+/// Checks `(sum(a_i * s_i))G = sum(a_i * R_i) + sum(a_i * e_i * P_i)` with a single multi-scalar
+/// multiplication, which is much faster than the separate verifications for the large batches.
+/// The weights `a_i` are derived from the hash of the whole batch, so they can't be chosen by the signers.
+pub fn schnorr_verify_batch(items: &[(XOnlyPublicKey, &[u8], SchnorrSignature)]) -> bool {
+	let mut hasher = tagged_hasher(BATCH_TAG);
+	for (key, msg, sig) in items {
+		hasher.update(&key.to_bytes());
+		hasher.update(&sig.to_bytes());
+		hasher.update(&(msg.len() as u64).to_be_bytes());
+		hasher.update(msg);
+	}
+	let seed = hasher.finalize();
+
+	let mut terms = Vec::with_capacity(2 * items.len() + 1);
+	let mut s_sum = Scalar::zero();
+
+	for (i, (key, msg, sig)) in items.iter().enumerate() {
+		let r = match SECP256K1.lift_x(&sig.r, false) {
+			Ok(r) => r,
+			Err(_) => return false
+		};
+		let e = challenge(&sig.r, &key.to_bytes(), msg);
+
+		// a_0 = 1, the rest are the hashes of the seed and the index
+		let a = if i == 0 {
+			Scalar::one()
+		} else {
+			let mut hasher = Sha256::new();
+			hasher.update(&seed);
+			hasher.update(&(i as u64).to_be_bytes());
+			Scalar::reduce(&Uint256::from_be_bytes(&hasher.finalize()))
+		};
+
+		s_sum = s_sum + a * sig.s;
+		terms.push((a, r));
+		terms.push((a * e, key.point));
+	}
+	terms.push((-s_sum, SECP256K1.gen_point()));
+
+	SECP256K1.multi_mul(&terms).is_identity()
+}
+
+/// Computes the challenge `e = hash_challenge(r || P || msg) (mod n)`.
+fn challenge(r: &FieldElement, p: &[u8; 32], msg: &[u8]) -> Scalar {
+	let mut hasher = tagged_hasher(CHALLENGE_TAG);
+	hasher.update(&r.to_bytes());
+	hasher.update(p);
+	hasher.update(msg);
+	Scalar::reduce(&Uint256::from_be_bytes(&hasher.finalize()))
+}
+
+fn is_odd(y: &FieldElement) -> bool {
+	y.to_uint().is_odd()
+}
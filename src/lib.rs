@@ -1,2 +1,3 @@
 pub mod utils;
 pub mod crypto;
+pub mod pow;
\ No newline at end of file
@@ -0,0 +1,300 @@
+use crypto::hash::{Blake2b, sha256d_uint};
+use utils::bignum::Uint256;
+use std::error::Error;
+use std::fmt;
+
+/// Equihash proof of work (the generalized birthday problem), as used by Zcash.
+///
+/// The solution is `2^k` distinct indices, the `n`-bit BLAKE2b hashes of which XOR to zero. The indices
+/// are ordered as the binary tree, every subtree of `2^r` indices has `r * n / (k + 1)` leading zero
+/// bits in the XOR of its hashes and its left half starts with the lesser index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Equihash {
+	n: u32,
+	k: u32
+}
+
+impl Equihash {
+	/// Creates Equihash with the hash length of `n` bits and `2^k` indices in the solution.
+	///
+	/// Returns `None` if `n` isn't the multiple of both 8 and `k + 1`, is greater than 512,
+	/// the indices don't fit into 31 bits or the solution length isn't the whole number of bytes.
+	pub fn new(n: u32, k: u32) -> Option<Self> {
+		if k == 0 || k >= 32 || n == 0 || n > 512 || !n.is_multiple_of(8) || !n.is_multiple_of(k + 1) {
+			return None;
+		}
+
+		let collision_bits = n / (k + 1);
+		if collision_bits + 1 > 31 || !((collision_bits as u64 + 1) << k).is_multiple_of(8) {
+			return None;
+		}
+
+		Some(Equihash { n, k })
+	}
+
+	/// Returns Equihash with `n = 200, k = 9`, as used by Zcash.
+	pub fn zcash() -> Self {
+		Equihash { n: 200, k: 9 }
+	}
+
+	/// Returns the hash length in bits.
+	pub fn n(&self) -> u32 { self.n }
+
+	/// Returns the binary logarithm of the number of the indices in the solution.
+	pub fn k(&self) -> u32 { self.k }
+
+	/// Returns the size of the encoded solution in bytes.
+	pub fn solution_size(&self) -> usize {
+		(self.index_bits() as usize) << self.k >> 3
+	}
+
+	/// Encodes the indices of the solution: every index takes `n / (k + 1) + 1` bits, big-endian.
+	///
+	/// Returns `None` if the number of the indices isn't `2^k` or some index doesn't fit.
+	pub fn encode_solution(&self, indices: &[u32]) -> Option<Vec<u8>> {
+		let bits = self.index_bits();
+		if indices.len() != 1 << self.k || indices.iter().any(|&i| i >> bits != 0) {
+			return None;
+		}
+
+		let mut res = Vec::with_capacity(self.solution_size());
+		let (mut acc, mut acc_bits) = (0u64, 0);
+		for &index in indices {
+			acc = acc << bits | index as u64;
+			acc_bits += bits;
+
+			while acc_bits >= 8 {
+				acc_bits -= 8;
+				res.push((acc >> acc_bits) as u8);
+			}
+		}
+
+		Some(res)
+	}
+
+	/// Decodes the indices of the solution.
+	///
+	/// Returns `None` if the solution has the wrong size.
+	pub fn decode_solution(&self, solution: &[u8]) -> Option<Vec<u32>> {
+		if solution.len() != self.solution_size() {
+			return None;
+		}
+
+		let bits = self.index_bits();
+		let mut res = Vec::with_capacity(1 << self.k);
+		let (mut acc, mut acc_bits) = (0u64, 0);
+		for &byte in solution {
+			acc = acc << 8 | byte as u64;
+			acc_bits += 8;
+
+			if acc_bits >= bits {
+				acc_bits -= bits;
+				res.push((acc >> acc_bits) as u32 & ((1 << bits) - 1));
+			}
+		}
+
+		Some(res)
+	}
+
+	/// Verifies the solution for the input, which is the block header without the solution.
+	pub fn verify(&self, input: &[u8], solution: &[u8]) -> Result<(), EquihashError> {
+		let indices = self.decode_solution(solution).ok_or(EquihashError::InvalidLength)?;
+
+		let mut sorted = indices.clone();
+		sorted.sort();
+		if sorted.windows(2).any(|pair| pair[0] == pair[1]) {
+			return Err(EquihashError::DuplicateIndices);
+		}
+
+		let hasher = self.hasher(input);
+		let mut rows: Vec<(Vec<u8>, u32)> = indices.iter().map(|&i| (self.leaf_hash(&hasher, i), i)).collect();
+
+		// Every round merges the neighbour subtrees, the first index of the subtree is kept for the ordering
+		for round in 1..=self.k {
+			let zero_bits = if round == self.k { self.n } else { round * self.collision_bits() };
+
+			rows = rows.chunks_exact(2).map(|pair| {
+				let hash = xor(&pair[0].0, &pair[1].0);
+				if leading_zeros(&hash) < zero_bits {
+					return Err(EquihashError::NoCollision);
+				}
+
+				if pair[0].1 >= pair[1].1 {
+					return Err(EquihashError::InvalidOrder);
+				}
+
+				Ok((hash, pair[0].1))
+			}).collect::<Result<_, _>>()?;
+		}
+
+		Ok(())
+	}
+
+	/// Returns the hash of the block for the comparison with the target: double SHA-256 of the input
+	/// followed by the solution with the compact size prefix, as in Zcash block header.
+	pub fn pow_hash(&self, input: &[u8], solution: &[u8]) -> Uint256 {
+		let mut data = Vec::with_capacity(input.len() + solution.len() + 5);
+		data.extend_from_slice(input);
+		write_compact_size(&mut data, solution.len() as u64);
+		data.extend_from_slice(solution);
+		sha256d_uint(&data)
+	}
+
+	/// Checks whether the solution is valid and the hash of the block doesn't exceed the target.
+	pub fn meets_target(&self, input: &[u8], solution: &[u8], target: &Uint256) -> bool {
+		self.verify(input, solution).is_ok() && self.pow_hash(input, solution) <= *target
+	}
+
+	/// Finds all solutions for the input with Wagner's algorithm and returns them encoded and sorted.
+	///
+	/// This is the reference solver: it keeps all `2^(n / (k + 1) + 1)` hashes in the memory and isn't
+	/// intended for the real parameters, only for the small ones (e.g. in tests).
+	pub fn solve(&self, input: &[u8]) -> Vec<Vec<u8>> {
+		let hasher = self.hasher(input);
+		let (hash_len, per_hash) = (self.n as usize / 8, self.indices_per_hash());
+		let count = 1u32 << self.index_bits();
+
+		let mut rows = Vec::with_capacity(count as usize);
+		for i in (0..count).step_by(per_hash as usize) {
+			let digest = self.block_hash(&hasher, i / per_hash);
+			for (j, hash) in digest.chunks_exact(hash_len).enumerate().take((count - i) as usize) {
+				rows.push((hash.to_vec(), vec![i + j as u32]));
+			}
+		}
+
+		for round in 1..=self.k {
+			// The last round collides on all remaining bits, the XOR becomes zero
+			let start = (round - 1) * self.collision_bits();
+			let len = if round == self.k { self.n - start } else { self.collision_bits() };
+			rows.sort_by_cached_key(|row| read_bits(&row.0, start, len));
+			let keys: Vec<u64> = rows.iter().map(|row| read_bits(&row.0, start, len)).collect();
+
+			let mut next = Vec::new();
+			let mut group = 0;
+			while group < rows.len() {
+				let end = group + keys[group..].iter().take_while(|&&key| key == keys[group]).count();
+
+				for a in group..end {
+					for b in a + 1..end {
+						let (left, right) = if rows[a].1[0] < rows[b].1[0] { (&rows[a], &rows[b]) } else { (&rows[b], &rows[a]) };
+						if left.1.iter().any(|i| right.1.contains(i)) {
+							continue;
+						}
+
+						let mut indices = left.1.clone();
+						indices.extend_from_slice(&right.1);
+						next.push((xor(&left.0, &right.0), indices));
+					}
+				}
+
+				group = end;
+			}
+
+			rows = next;
+		}
+
+		let mut res: Vec<Vec<u8>> = rows.iter().filter_map(|row| self.encode_solution(&row.1)).collect();
+		res.sort();
+		res.dedup();
+		res
+	}
+
+	fn collision_bits(&self) -> u32 {
+		self.n / (self.k + 1)
+	}
+
+	fn index_bits(&self) -> u32 {
+		self.collision_bits() + 1
+	}
+
+	/// Number of the `n`-bit hashes taken from one BLAKE2b digest.
+	fn indices_per_hash(&self) -> u32 {
+		512 / self.n
+	}
+
+	/// BLAKE2b with the personalization `"ZcashPoW" || n || k` after processing the input.
+	fn hasher(&self, input: &[u8]) -> Blake2b {
+		let mut personal = [0u8; 16];
+		personal[..8].copy_from_slice(b"ZcashPoW");
+		personal[8..12].copy_from_slice(&self.n.to_le_bytes());
+		personal[12..].copy_from_slice(&self.k.to_le_bytes());
+
+		let mut hasher = Blake2b::with_params((self.indices_per_hash() * self.n / 8) as usize, &[], &personal);
+		hasher.update(input);
+		hasher
+	}
+
+	/// Returns the digest, which contains the hashes of `indices_per_hash` consecutive indices.
+	fn block_hash(&self, hasher: &Blake2b, block: u32) -> Vec<u8> {
+		let mut hasher = hasher.clone();
+		hasher.update(&block.to_le_bytes());
+		hasher.finalize()
+	}
+
+	fn leaf_hash(&self, hasher: &Blake2b, index: u32) -> Vec<u8> {
+		let hash_len = self.n as usize / 8;
+		let pos = (index % self.indices_per_hash()) as usize * hash_len;
+		self.block_hash(hasher, index / self.indices_per_hash())[pos..pos + hash_len].to_vec()
+	}
+}
+
+/// Error of Equihash solution verification.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EquihashError {
+	/// Solution has the wrong size.
+	InvalidLength,
+
+	/// Solution contains the same index more than once.
+	DuplicateIndices,
+
+	/// Hashes of the subtrees don't collide on the required bits.
+	NoCollision,
+
+	/// Left subtree doesn't start with the lesser index.
+	InvalidOrder
+}
+
+impl fmt::Display for EquihashError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			EquihashError::InvalidLength => write!(f, "Equihash solution has the wrong size."),
+			EquihashError::DuplicateIndices => write!(f, "Equihash solution contains the duplicate indices."),
+			EquihashError::NoCollision => write!(f, "Equihash solution hashes do not collide."),
+			EquihashError::InvalidOrder => write!(f, "Equihash solution indices are in the wrong order.")
+		}
+	}
+}
+
+impl Error for EquihashError { }
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+	a.iter().zip(b.iter()).map(|(a, b)| a ^ b).collect()
+}
+
+fn leading_zeros(bytes: &[u8]) -> u32 {
+	match bytes.iter().position(|&b| b != 0) {
+		Some(pos) => pos as u32 * 8 + bytes[pos].leading_zeros(),
+		None => bytes.len() as u32 * 8
+	}
+}
+
+/// Reads `len` bits (at most 64) starting from the bit `start`, big-endian.
+fn read_bits(bytes: &[u8], start: u32, len: u32) -> u64 {
+	(start..start + len).fold(0, |acc, i| acc << 1 | (bytes[i as usize / 8] >> (7 - i % 8) & 1) as u64)
+}
+
+/// Writes the length in the compact size format of Bitcoin serialization.
+fn write_compact_size(data: &mut Vec<u8>, len: u64) {
+	if len < 0xfd {
+		data.push(len as u8);
+	} else if len <= 0xffff {
+		data.push(0xfd);
+		data.extend_from_slice(&(len as u16).to_le_bytes());
+	} else if len <= 0xffff_ffff {
+		data.push(0xfe);
+		data.extend_from_slice(&(len as u32).to_le_bytes());
+	} else {
+		data.push(0xff);
+		data.extend_from_slice(&len.to_le_bytes());
+	}
+}
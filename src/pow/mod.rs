@@ -0,0 +1,25 @@
+mod sha256d;
+mod scrypt;
+mod equihash;
+
+pub use self::sha256d::Sha256dPow;
+pub use self::scrypt::ScryptPow;
+pub use self::equihash::{Equihash, EquihashError};
+
+use utils::bignum::Uint256;
+
+/// Size of the block header in bytes.
+pub const HEADER_SIZE: usize = 80;
+
+/// Proof-of-work hash function of the block header.
+///
+/// The trait is object safe, so the chain parameters can keep the algorithm as `Box<dyn PowHasher>`.
+pub trait PowHasher {
+	/// Returns the proof-of-work hash of the header as the number, which is compared to the target.
+	fn hash(&self, header: &[u8; HEADER_SIZE]) -> Uint256;
+
+	/// Checks whether the hash of the header doesn't exceed the target.
+	fn meets_target(&self, header: &[u8; HEADER_SIZE], target: &Uint256) -> bool {
+		self.hash(header) <= *target
+	}
+}
@@ -0,0 +1,54 @@
+use super::{PowHasher, HEADER_SIZE};
+use crypto::hash::scrypt;
+use utils::bignum::Uint256;
+
+/// scrypt proof of work: the header is both the password and the salt, the 32-byte output
+/// is read as the little-endian number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScryptPow {
+	n: usize,
+	r: usize,
+	p: usize
+}
+
+impl ScryptPow {
+	/// Creates scrypt proof of work with the given cost `n`, block size `r` and parallelization `p`.
+	///
+	/// Returns `None` if `n` isn't the power of two greater than one, `r` or `p` is zero or `p * r >= 2^30`.
+	pub fn new(n: usize, r: usize, p: usize) -> Option<Self> {
+		if n > 1 && n.is_power_of_two() && r > 0 && p > 0 && (p as u64) * (r as u64) < 1 << 30 {
+			Some(ScryptPow { n, r, p })
+		} else {
+			None
+		}
+	}
+
+	/// Returns scrypt proof of work with `N = 1024, r = 1, p = 1`, as used by Litecoin.
+	pub fn litecoin() -> Self {
+		ScryptPow { n: 1024, r: 1, p: 1 }
+	}
+
+	/// Returns the cost parameter.
+	pub fn n(&self) -> usize { self.n }
+
+	/// Returns the block size parameter.
+	pub fn r(&self) -> usize { self.r }
+
+	/// Returns the parallelization parameter.
+	pub fn p(&self) -> usize { self.p }
+}
+
+impl Default for ScryptPow {
+	fn default() -> Self {
+		Self::litecoin()
+	}
+}
+
+impl PowHasher for ScryptPow {
+	fn hash(&self, header: &[u8; HEADER_SIZE]) -> Uint256 {
+		let mut digest = [0u8; 32];
+		digest.copy_from_slice(&scrypt(header, header, self.n, self.r, self.p, 32));
+		digest.reverse();
+		Uint256::from_be_bytes(&digest)
+	}
+}
@@ -0,0 +1,13 @@
+use super::{PowHasher, HEADER_SIZE};
+use crypto::hash::sha256d_uint;
+use utils::bignum::Uint256;
+
+/// Double SHA-256 proof of work, as used by Bitcoin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sha256dPow;
+
+impl PowHasher for Sha256dPow {
+	fn hash(&self, header: &[u8; HEADER_SIZE]) -> Uint256 {
+		sha256d_uint(header)
+	}
+}
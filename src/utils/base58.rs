@@ -0,0 +1,107 @@
+use crypto::hash::sha256d;
+use std::error::Error;
+use std::fmt;
+
+/// Bitcoin Base58 alphabet: the alphanumeric characters without `0`, `O`, `I` and `l`.
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Size of Base58Check checksum in bytes.
+pub const CHECKSUM_SIZE: usize = 4;
+
+/// Encodes the data in Base58, every leading zero byte is encoded as `1`.
+pub fn encode(data: &[u8]) -> String {
+	let zeros = data.iter().take_while(|&&b| b == 0).count();
+
+	// Base58 digits of the number, the least significant first
+	let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+	for &byte in &data[zeros..] {
+		let mut carry = byte as u32;
+		for digit in digits.iter_mut() {
+			carry += (*digit as u32) << 8;
+			*digit = (carry % 58) as u8;
+			carry /= 58;
+		}
+
+		while carry > 0 {
+			digits.push((carry % 58) as u8);
+			carry /= 58;
+		}
+	}
+
+	let mut res = String::with_capacity(zeros + digits.len());
+	res.extend(std::iter::repeat_n('1', zeros));
+	res.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+	res
+}
+
+/// Decodes the Base58 string.
+pub fn decode(s: &str) -> Result<Vec<u8>, Base58Error> {
+	let zeros = s.bytes().take_while(|&c| c == b'1').count();
+
+	// Bytes of the number, the least significant first
+	let mut bytes: Vec<u8> = Vec::with_capacity(s.len() * 733 / 1000 + 1);
+	for (pos, c) in s.char_indices().skip(zeros) {
+		let mut carry = ALPHABET.iter().position(|&a| a as char == c).ok_or(Base58Error::InvalidCharacter(pos))? as u32;
+		for byte in bytes.iter_mut() {
+			carry += *byte as u32 * 58;
+			*byte = carry as u8;
+			carry >>= 8;
+		}
+
+		while carry > 0 {
+			bytes.push(carry as u8);
+			carry >>= 8;
+		}
+	}
+
+	let mut res = vec![0u8; zeros];
+	res.extend(bytes.iter().rev());
+	Ok(res)
+}
+
+/// Encodes the data in Base58Check: the data is followed by the first 4 bytes of its double SHA-256.
+pub fn encode_check(data: &[u8]) -> String {
+	let mut payload = data.to_vec();
+	payload.extend_from_slice(&sha256d(data)[..CHECKSUM_SIZE]);
+	encode(&payload)
+}
+
+/// Decodes the Base58Check string, verifies and removes the checksum.
+pub fn decode_check(s: &str) -> Result<Vec<u8>, Base58Error> {
+	let mut data = decode(s)?;
+	if data.len() < CHECKSUM_SIZE {
+		return Err(Base58Error::TooShort);
+	}
+
+	let checksum = data.split_off(data.len() - CHECKSUM_SIZE);
+	if sha256d(&data)[..CHECKSUM_SIZE] != checksum[..] {
+		return Err(Base58Error::InvalidChecksum);
+	}
+
+	Ok(data)
+}
+
+/// Error of Base58 decoding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Base58Error {
+	/// String contains the character out of the alphabet at the given byte position.
+	InvalidCharacter(usize),
+
+	/// Base58Check data is shorter than the checksum.
+	TooShort,
+
+	/// Base58Check checksum doesn't match the data.
+	InvalidChecksum
+}
+
+impl fmt::Display for Base58Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			Base58Error::InvalidCharacter(pos) => write!(f, "Invalid Base58 character at position {}.", pos),
+			Base58Error::TooShort => write!(f, "Base58Check data is too short."),
+			Base58Error::InvalidChecksum => write!(f, "Base58Check checksum is invalid.")
+		}
+	}
+}
+
+impl Error for Base58Error { }
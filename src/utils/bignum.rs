@@ -21,20 +21,16 @@ pub trait One: Sized {
 	fn is_one(&self) -> bool;
 }
 
-// TODO: write doc comment
-///
+/// Returns low 32 bits of the number.
 fn lo(x: u64) -> u32 { (x & ((1u64 << 32) - 1)) as u32 }
 
-// TODO: write doc comment
-///
+/// Returns high 32 bits of the number.
 fn hi(x: u64) -> u32 { (x >> 32) as u32 }
 
-// TODO write doc comment
-///
+/// Returns `i`-th item of the slice or zero if it is out of bounds.
 fn item_or_zero(slice: &[u64], i:usize) -> u64 { if i < slice.len() { slice[i] } else { 0 } }
 
-// TODO: write doc comment
-///
+/// Multiplies two `u64` numbers, returns `(result, carry)`.
 fn mul_u64_carry(a: u64, b: u64) -> (u64, u64) {
 	// Every `s` represents 32bit part of the resulting number
 	let (s0, mut s1, mut s2, s3);
@@ -153,7 +149,7 @@ macro_rules! bn_op {
 		// i ∈ [n-1; 0]
 		for i in (0..$res_size).rev() {
 			// t = (rb + U_j)
-			let t = ($a[i] as u128) + (r * (u64::max_value() as u128 + 1));
+			let t = ($a[i] as u128) + (r * (u64::MAX as u128 + 1));
 
 			// q_i = t / V
 			q[i] = (t / b) as u64;
@@ -224,6 +220,41 @@ macro_rules! impl_bignum {
 			pub fn is_even(&self) -> bool {
 				self.0[0] & 1 == 0
 			}
+
+			/// Returns `i`-th bit of the number, the bits beyond the size are zero.
+			pub fn bit(&self, i: usize) -> bool {
+				i < $size * 64 && (self.0[i / 64] >> (i % 64)) & 1 == 1
+			}
+
+			/// Counts significant bits in the number.
+			pub fn bits(&self) -> usize {
+				let digits = self.count_digits();
+				digits * 64 - self.0[digits - 1].leading_zeros() as usize
+			}
+
+			/// Creates number from the bytes in the big-endian order.
+			pub fn from_be_bytes(bytes: &[u8; $size * 8]) -> Self {
+				let mut arr = [0u64; $size];
+
+				for (i, chunk) in bytes.rchunks(8).enumerate() {
+					let mut digit = [0u8; 8];
+					digit.copy_from_slice(chunk);
+					arr[i] = u64::from_be_bytes(digit);
+				}
+
+				$name(arr)
+			}
+
+			/// Returns bytes of the number in the big-endian order.
+			pub fn to_be_bytes(&self) -> [u8; $size * 8] {
+				let mut bytes = [0u8; $size * 8];
+
+				for (i, chunk) in bytes.rchunks_mut(8).enumerate() {
+					chunk.copy_from_slice(&self.0[i].to_be_bytes());
+				}
+
+				bytes
+			}
 		}
 
 		impl Default for $name {
@@ -253,7 +284,7 @@ macro_rules! impl_bignum {
 
 				for num in data.iter().rev() {
 					if *num != 0u64 {
-						write!(f, "{}", num);
+						write!(f, "{}", num)?;
 					}
 				}
 
@@ -281,7 +312,7 @@ macro_rules! impl_bignum {
 
 		impl FromStr for $name {
 			type Err = ();
-			fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+			fn from_str(_s: &str) -> Result<Self, <Self as FromStr>::Err> {
 				unimplemented!()
 			}
 		}
@@ -301,7 +332,7 @@ macro_rules! impl_bignum {
 		impl Eq for $name { }
 
 		impl PartialOrd for $name {
-			fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(bn_raw_cmp(&self.0, &other.0)) }
+			fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 		}
 
 		impl Ord for $name {
@@ -352,9 +383,9 @@ macro_rules! impl_bignum {
 				let b = [0, 1];
 
 				// d = floor((b-1) / V_(n-1))
-				let halfb = u64::max_value() / 2 + 1;
+				let halfb = u64::MAX / 2 + 1;
 				let d = if other.0[n - 1] > halfb {
-					u64::max_value() / other.0[n - 1]
+					u64::MAX / other.0[n - 1]
 				} else {
 					halfb / other.0[n - 1]
 				};
@@ -458,11 +489,13 @@ macro_rules! impl_bignum {
 			}
 
 			fn is_one(&self) -> bool {
-				if self.0[0] == 1 {
-					for i in 1..$size {
-						if self.0[i] != 0 {
-							return false;
-						}
+				if self.0[0] != 1 {
+					return false;
+				}
+
+				for i in 1..$size {
+					if self.0[i] != 0 {
+						return false;
 					}
 				}
 
@@ -525,7 +558,7 @@ macro_rules! sign_wrap {
 
 		impl From<i64> for $name {
 			fn from(num: i64) -> Self {
-				Self::from_raw($base::from(num.abs() as u32), num >= 0)
+				Self::from_raw($base::from(num.unsigned_abs()), num >= 0)
 			}
 		}
 
@@ -663,9 +696,7 @@ impl From<Uint512> for Uint256 {
 
 		let mut arr = [0u64; U256_SIZE];
 
-		for i in 0..U256_SIZE {
-			arr[i] = n.0[i];
-		}
+		arr.copy_from_slice(&n.0[..U256_SIZE]);
 
 		Uint256(arr)
 	}
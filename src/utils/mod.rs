@@ -1 +1,2 @@
-pub mod bignum;
\ No newline at end of file
+pub mod bignum;
+pub mod base58;
\ No newline at end of file
@@ -0,0 +1,51 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::utils::base58::{encode, decode, encode_check, decode_check, Base58Error};
+use common::unhex;
+
+#[test]
+fn test_base58() {
+	// Bitcoin Core test vectors
+	let vectors = [
+		("", ""),
+		("61", "2g"),
+		("626262", "a3gV"),
+		("636363", "aPEr"),
+		("73696d706c792061206c6f6e6720737472696e67", "2cFupjhnEsSn59qHXstmK2ffpLv2"),
+		("00eb15231dfceb60925886b67d065299925915aeb172c06647", "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L"),
+		("516b6fcd0f", "ABnLTmg"),
+		("bf4f89001e670274dd", "3SEo3LWLoPntC"),
+		("572e4794", "3EFU7m"),
+		("ecac89cad93923c02321", "EJDM8drfXA6uyA"),
+		("10c8511e", "Rt5zm"),
+		("00000000000000000000", "1111111111")
+	];
+
+	for &(hex, s) in vectors.iter() {
+		assert_eq!(encode(&unhex(hex)), s);
+		assert_eq!(decode(s), Ok(unhex(hex)));
+	}
+
+	assert_eq!(decode("1l1"), Err(Base58Error::InvalidCharacter(1)));
+	assert_eq!(decode("a0"), Err(Base58Error::InvalidCharacter(1)));
+	assert_eq!(decode("O"), Err(Base58Error::InvalidCharacter(0)));
+	assert_eq!(decode("2g "), Err(Base58Error::InvalidCharacter(2)));
+}
+
+#[test]
+fn test_base58_check() {
+	assert_eq!(encode_check(b"hello"), "2L5B5yqsVG8Vt");
+	assert_eq!(decode_check("2L5B5yqsVG8Vt"), Ok(b"hello".to_vec()));
+
+	// Address of the genesis block coinbase
+	let data = decode_check("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa").unwrap();
+	assert_eq!(data, unhex("0062e907b15cbf27d5425399ebf6f0fb50ebb88f18"));
+	assert_eq!(encode_check(&data), "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+
+	assert_eq!(decode_check("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"), Err(Base58Error::InvalidChecksum));
+	assert_eq!(decode_check("2L5B5yqsVG8Vu"), Err(Base58Error::InvalidChecksum));
+	assert_eq!(decode_check("111"), Err(Base58Error::TooShort));
+	assert_eq!(decode_check(""), Err(Base58Error::TooShort));
+}
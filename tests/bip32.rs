@@ -0,0 +1,223 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::crypto::bip32::{ExtendedPrivKey, ExtendedPubKey, DerivationPath, Bip32Error, HARDENED};
+use metamint::crypto::bip32::{XPRV_VERSION, XPUB_VERSION, TPRV_VERSION, TPUB_VERSION};
+use metamint::crypto::ecdsa::PointError;
+use metamint::crypto::field::PrimeField;
+use metamint::utils::base58::{self, Base58Error};
+use common::{unhex, hex};
+
+/// Checks the chain of the derived keys against the expected serializations.
+fn check_chain(seed: &str, path: &[u32], keys: &[(&str, &str)]) {
+	let mut key = ExtendedPrivKey::new_master(&unhex(seed)).unwrap();
+
+	for (i, &(xprv, xpub)) in keys.iter().enumerate() {
+		if i > 0 {
+			let index = path[i - 1];
+			let parent = key.public_key();
+			key = key.derive_child(index).unwrap();
+
+			// The normal children can be derived from the public key too
+			if index < HARDENED {
+				assert_eq!(parent.derive_child(index), Ok(key.public_key()));
+			} else {
+				assert_eq!(parent.derive_child(index), Err(Bip32Error::HardenedFromPublic));
+			}
+
+			assert_eq!(key.parent_fingerprint(), parent.fingerprint());
+			assert_eq!(key.child_number(), index);
+		}
+
+		assert_eq!(key.depth() as usize, i);
+		assert_eq!(key.encode(XPRV_VERSION), xprv);
+		assert_eq!(key.public_key().encode(XPUB_VERSION), xpub);
+
+		let decoded = ExtendedPrivKey::decode(xprv, XPRV_VERSION).unwrap();
+		assert_eq!(decoded.encode(XPRV_VERSION), xprv);
+		assert_eq!(decoded.public_key(), key.public_key());
+		assert_eq!(ExtendedPubKey::decode(xpub, XPUB_VERSION), Ok(key.public_key()));
+	}
+
+	let path = DerivationPath::new(path.to_vec());
+	let master = ExtendedPrivKey::new_master(&unhex(seed)).unwrap();
+	assert_eq!(master.derive_path(&path).unwrap().encode(XPRV_VERSION), keys[keys.len() - 1].0);
+}
+
+#[test]
+fn test_vector_1() {
+	// BIP32 test vector 1: m/0'/1/2'/2/1000000000
+	check_chain("000102030405060708090a0b0c0d0e0f", &[HARDENED, 1, HARDENED | 2, 2, 1000000000], &[
+		(
+			"xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+			"xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+		),
+		(
+			"xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+			"xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw"
+		),
+		(
+			"xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+			"xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ"
+		),
+		(
+			"xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+			"xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5"
+		),
+		(
+			"xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+			"xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV"
+		),
+		(
+			"xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+			"xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy"
+		)
+	]);
+}
+
+#[test]
+fn test_vector_2() {
+	// BIP32 test vector 2: m/0/2147483647'/1/2147483646'/2
+	check_chain(
+		"fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+		&[0, HARDENED | 2147483647, 1, HARDENED | 2147483646, 2],
+		&[
+			(
+				"xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+				"xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB"
+			),
+			(
+				"xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+				"xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH"
+			),
+			(
+				"xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+				"xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a"
+			),
+			(
+				"xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+				"xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon"
+			),
+			(
+				"xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+				"xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL"
+			),
+			(
+				"xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+				"xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt"
+			)
+		]
+	);
+}
+
+#[test]
+fn test_fingerprint() {
+	let master = ExtendedPrivKey::new_master(&unhex("000102030405060708090a0b0c0d0e0f")).unwrap();
+	assert_eq!(hex(&master.identifier()), "3442193e1bb70916e914552172cd4e2dbc9df811");
+	assert_eq!(master.fingerprint(), [0x34, 0x42, 0x19, 0x3e]);
+	assert_eq!(master.public_key().fingerprint(), master.fingerprint());
+	assert_eq!(master.parent_fingerprint(), [0; 4]);
+	assert_eq!(master.derive_child(HARDENED).unwrap().parent_fingerprint(), [0x34, 0x42, 0x19, 0x3e]);
+	assert_eq!(
+		hex(master.chain_code()),
+		"873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508"
+	);
+	assert_eq!(
+		hex(&master.private_key().scalar().to_bytes()),
+		"e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+	);
+}
+
+#[test]
+fn test_derivation_path() {
+	let path: DerivationPath = "m/44'/0'/0'/0/5".parse().unwrap();
+	assert_eq!(path.indices(), &[HARDENED | 44, HARDENED, HARDENED, 0, 5]);
+	assert_eq!(path.to_string(), "m/44'/0'/0'/0/5");
+	assert_eq!("m/44h/0h/0h/0/5".parse(), Ok(path));
+
+	assert_eq!("m".parse(), Ok(DerivationPath::default()));
+	assert_eq!("m/2147483647'".parse::<DerivationPath>().unwrap().indices(), &[u32::MAX]);
+
+	for s in ["", "M/0", "m/", "m//1", "0/1", "m/2147483648", "m/-1", "m/+1", "m/1''", "m/1'h", "m/0x1", "m/1 ", "m/a"].iter() {
+		assert_eq!(s.parse::<DerivationPath>(), Err(Bip32Error::InvalidPath));
+	}
+
+	// The public key derivation by the path with the normal indices only
+	let master = ExtendedPrivKey::new_master(&unhex("000102030405060708090a0b0c0d0e0f")).unwrap();
+	let account = master.derive_path(&"m/44'/0'/0'".parse().unwrap()).unwrap();
+	let path: DerivationPath = "m/0/5".parse().unwrap();
+	assert_eq!(account.public_key().derive_path(&path), Ok(account.derive_path(&path).unwrap().public_key()));
+	assert_eq!(
+		account.public_key().derive_path(&"m/0'/5".parse().unwrap()),
+		Err(Bip32Error::HardenedFromPublic)
+	);
+	assert_eq!(
+		account.derive_path(&path).unwrap().encode(XPRV_VERSION),
+		master.derive_path(&"m/44'/0'/0'/0/5".parse().unwrap()).unwrap().encode(XPRV_VERSION)
+	);
+}
+
+#[test]
+fn test_versions() {
+	let master = ExtendedPrivKey::new_master(&unhex("000102030405060708090a0b0c0d0e0f")).unwrap();
+
+	let tprv = master.encode(TPRV_VERSION);
+	let tpub = master.public_key().encode(TPUB_VERSION);
+	assert!(tprv.starts_with("tprv"));
+	assert!(tpub.starts_with("tpub"));
+	assert_eq!(ExtendedPrivKey::decode(&tprv, TPRV_VERSION).unwrap().encode(XPRV_VERSION), master.encode(XPRV_VERSION));
+	assert_eq!(ExtendedPubKey::decode(&tpub, TPUB_VERSION), Ok(master.public_key()));
+
+	assert_eq!(ExtendedPrivKey::decode(&tprv, XPRV_VERSION).unwrap_err(), Bip32Error::InvalidVersion);
+	assert_eq!(ExtendedPubKey::decode(&tpub, XPUB_VERSION), Err(Bip32Error::InvalidVersion));
+	assert_eq!(ExtendedPubKey::decode(&master.encode(XPRV_VERSION), XPRV_VERSION), Err(Bip32Error::InvalidEncoding));
+}
+
+#[test]
+fn test_invalid() {
+	assert_eq!(ExtendedPrivKey::new_master(&[0; 15]).unwrap_err(), Bip32Error::InvalidSeedLength);
+	assert_eq!(ExtendedPrivKey::new_master(&[0; 65]).unwrap_err(), Bip32Error::InvalidSeedLength);
+	assert!(ExtendedPrivKey::new_master(&[0; 16]).is_ok());
+
+	let master = ExtendedPrivKey::new_master(&unhex("000102030405060708090a0b0c0d0e0f")).unwrap();
+	let mut data = base58::decode_check(&master.encode(XPRV_VERSION)).unwrap();
+	let decode = |data: &[u8]| ExtendedPrivKey::decode(&base58::encode_check(data), XPRV_VERSION).map(|key| key.encode(XPRV_VERSION));
+
+	assert_eq!(decode(&data[..77]), Err(Bip32Error::InvalidEncoding));
+
+	// The master key with the parent fingerprint or the child number
+	data[5] = 1;
+	assert_eq!(decode(&data), Err(Bip32Error::InvalidEncoding));
+	data[5] = 0;
+	data[12] = 1;
+	assert_eq!(decode(&data), Err(Bip32Error::InvalidEncoding));
+	data[12] = 0;
+
+	// The private key is zero, not less than the order or has the wrong prefix
+	let key = data[45..].to_vec();
+	data[45..].copy_from_slice(&[0; 33]);
+	assert_eq!(decode(&data), Err(Bip32Error::InvalidKey));
+	data[46..].copy_from_slice(&unhex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"));
+	assert_eq!(decode(&data), Err(Bip32Error::InvalidKey));
+	data[45..].copy_from_slice(&key);
+	data[45] = 1;
+	assert_eq!(decode(&data), Err(Bip32Error::InvalidEncoding));
+
+	// The public key isn't on the curve or isn't compressed
+	let mut data = base58::decode_check(&master.public_key().encode(XPUB_VERSION)).unwrap();
+	data[46..].copy_from_slice(&[0; 32]);
+	data[77] = 0x05;
+	assert_eq!(
+		ExtendedPubKey::decode(&base58::encode_check(&data), XPUB_VERSION),
+		Err(Bip32Error::InvalidPoint(PointError::NotOnCurve))
+	);
+	data[45] = 0x04;
+	assert_eq!(ExtendedPubKey::decode(&base58::encode_check(&data), XPUB_VERSION), Err(Bip32Error::InvalidEncoding));
+
+	let mut xpub = master.public_key().encode(XPUB_VERSION);
+	xpub.pop();
+	xpub.push('9');
+	assert_eq!(ExtendedPubKey::decode(&xpub, XPUB_VERSION), Err(Bip32Error::Base58(Base58Error::InvalidChecksum)));
+	assert_eq!(ExtendedPubKey::decode("xpub0", XPUB_VERSION), Err(Bip32Error::Base58(Base58Error::InvalidCharacter(4))));
+}
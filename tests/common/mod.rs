@@ -0,0 +1,18 @@
+#![allow(dead_code)]
+
+/// Decodes the hex string.
+pub fn unhex(hex: &str) -> Vec<u8> {
+	(0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+/// Encodes the bytes as the lowercase hex string.
+pub fn hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decodes the hex string of exactly `N` bytes.
+pub fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+	let mut res = [0u8; N];
+	res.copy_from_slice(&unhex(hex));
+	res
+}
@@ -0,0 +1,171 @@
+#[macro_use]
+extern crate metamint;
+
+mod common;
+
+use metamint::crypto::ecdsa::{Curve, EllipticCurve, CurveError, PrivateKey, PointFormat, SECP256K1, SECP256R1, SECP224K1};
+use metamint::crypto::ecdsa::{Secp256r1, Secp224k1, FieldElement, Scalar, Secp256r1Scalar, Secp224k1Scalar};
+use metamint::crypto::field::{PrimeField, FieldModulus, Fp};
+use metamint::crypto::hash::sha256;
+use metamint::utils::bignum::{Uint256, One};
+use common::{unhex, bytes};
+
+#[test]
+fn test_parameters() {
+	assert!(SECP256R1.gen_point().is_on_curve());
+	assert!(SECP256R1.gen_point().is_in_subgroup());
+	assert_eq!(SECP256R1.cofactor(), 1);
+
+	assert!(SECP224K1.gen_point().is_on_curve());
+	assert!(SECP224K1.gen_point().is_in_subgroup());
+	assert!(SECP224K1.order() > SECP224K1.modulo());
+}
+
+#[test]
+fn test_secp256r1() {
+	// RFC 6979, A.2.5: P-256 with SHA-256
+	let d = Secp256r1Scalar::from_bytes(&bytes("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")).unwrap();
+	let key = PrivateKey::<Secp256r1>::new(d).unwrap();
+	let public = key.public_key();
+
+	assert_eq!(
+		public.encode(PointFormat::Uncompressed),
+		unhex("0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299")
+	);
+
+	let hash = sha256(b"sample");
+	let sig = key.sign(&hash);
+	assert_eq!(
+		sig.to_compact(),
+		unhex("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8")
+	);
+	assert!(public.verify(&hash, &sig));
+	assert!(!public.verify(&sha256(b"test"), &sig));
+
+	let recoverable = key.sign_recoverable(&hash);
+	assert_eq!(recoverable.recover::<Secp256r1>(&hash), Ok(public));
+
+	let compressed = public.encode(PointFormat::Compressed);
+	assert_eq!(SECP256R1.decode_point(&compressed, false), Ok(*public.point()));
+}
+
+#[test]
+fn test_secp224k1() {
+	let g = SECP224K1.gen_point();
+	let k = Secp224k1Scalar::reduce(&Uint256::from_be_bytes(&sha256(b"secp224k1")));
+
+	// The ladder, the variable time and the table multiplication agree
+	assert_eq!(g * k, g.mul_vartime(&k));
+	assert_eq!(g * k, SECP224K1.mul_generator(&k));
+	assert_eq!(SECP224K1.mul_generator(&-Secp224k1Scalar::one()), -g);
+
+	let key = PrivateKey::<Secp224k1>::new(k).unwrap();
+	let public = key.public_key();
+	let hash = sha256(b"message");
+
+	let sig = key.sign_recoverable(&hash);
+	assert!(public.verify(&hash, sig.signature()));
+	assert_eq!(sig.recover::<Secp224k1>(&hash), Ok(public));
+
+	// The order is 225 bits long, the field is 224 bits long
+	assert_eq!(sig.signature().to_compact().len(), 58);
+	assert_eq!(public.encode(PointFormat::Compressed).len(), 29);
+
+	// p = 1 (mod 4), the compressed points are restored by the Tonelli-Shanks algorithm
+	for format in [PointFormat::Compressed, PointFormat::Uncompressed].iter() {
+		let encoded = public.encode(*format);
+		assert_eq!(SECP224K1.decode_point(&encoded, false), Ok(*public.point()));
+	}
+}
+
+runtime_curve!(
+	/// Secp256k1 defined at the runtime.
+	Runtime(FieldElement, Scalar)
+);
+
+#[test]
+fn test_new() {
+	let gen = SECP256K1.gen_point();
+	let (a, b, x, y) = (*SECP256K1.a(), *SECP256K1.b(), *gen.x(), *gen.y());
+
+	assert!(!Runtime::is_registered());
+	let curve = Runtime::register(a, b, x, y, 1).unwrap();
+	assert!(Runtime::is_registered());
+	assert_eq!(Runtime::register(a, b, x, y, 1).err(), Some(CurveError::AlreadyRegistered));
+	assert!(std::ptr::eq(curve, Runtime::curve()));
+
+	assert_eq!(curve.order(), SECP256K1.order());
+	assert_eq!(curve.cofactor(), 1);
+
+	let k = Scalar::reduce(&Uint256::from_be_bytes(&sha256(b"runtime")));
+	let p = curve.mul_generator(&k);
+	let q = SECP256K1.mul_generator(&k);
+	assert_eq!((p.x(), p.y()), (q.x(), q.y()));
+	assert_eq!(curve.gen_point() * k, p);
+	assert_eq!(p.double(), p + p);
+
+	let key = PrivateKey::<Runtime>::new(k).unwrap();
+	let hash = sha256(b"message");
+	assert!(key.public_key().verify(&hash, &key.sign(&hash)));
+
+	let gen = SECP224K1.gen_point();
+	assert!(EllipticCurve::<Secp224k1>::new(*SECP224K1.a(), *SECP224K1.b(), *gen.x(), *gen.y(), 1).is_ok());
+}
+
+#[test]
+fn test_new_errors() {
+	/// secp256k1 `p + 2 = 0 (mod 15)`.
+	#[derive(Debug)]
+	struct CompositeModulo;
+
+	impl FieldModulus for CompositeModulo {
+		const MODULO: Uint256 = Uint256([0xfffffffefffffc31, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]);
+	}
+
+	/// secp256k1 `n + 2 = 0 (mod 3)`.
+	#[derive(Debug)]
+	struct CompositeOrder;
+
+	impl FieldModulus for CompositeOrder {
+		const MODULO: Uint256 = Uint256([0xbfd25e8cd0364143, 0xbaaedce6af48a03b, 0xfffffffffffffffe, 0xffffffffffffffff]);
+	}
+
+	#[derive(Debug)]
+	struct SmallOrder;
+
+	impl FieldModulus for SmallOrder {
+		const MODULO: Uint256 = Uint256([101, 0, 0, 0]);
+	}
+
+	runtime_curve!(CompositeModuloCurve(Fp<CompositeModulo>, Scalar));
+	runtime_curve!(CompositeOrderCurve(FieldElement, Fp<CompositeOrder>));
+	runtime_curve!(SmallOrderCurve(FieldElement, Fp<SmallOrder>));
+	runtime_curve!(Unregistered(FieldElement, Scalar));
+
+	let gen = SECP256K1.gen_point();
+	let (a, b, x, y) = (*SECP256K1.a(), *SECP256K1.b(), *gen.x(), *gen.y());
+	let new = Unregistered::register;
+
+	let composite = Fp::<CompositeModulo>::one();
+	assert_eq!(
+		CompositeModuloCurve::register(composite, composite, composite, composite, 1).err(),
+		Some(CurveError::ModuloNotPrime)
+	);
+	assert_eq!(CompositeOrderCurve::register(a, b, x, y, 1).err(), Some(CurveError::OrderNotPrime));
+	assert_eq!(SmallOrderCurve::register(a, b, x, y, 1).err(), Some(CurveError::OrderTooSmall));
+
+	// y^2 = x^3
+	assert_eq!(new(a, FieldElement::from(0), x, y, 1).err(), Some(CurveError::SingularCurve));
+
+	assert_eq!(new(a, b, x, y, 0).err(), Some(CurveError::InvalidCofactor));
+	assert_eq!(new(a, b, x, y, 2).err(), Some(CurveError::InvalidCofactor));
+	assert_eq!(new(a, b, x, -x, 1).err(), Some(CurveError::GeneratorNotOnCurve));
+
+	// The point of y^2 = x^3 + 5 doesn't have the order of secp256k1
+	let x = FieldElement::from(3);
+	let y = (x.square() * x + FieldElement::from(5)).sqrt().unwrap();
+	assert_eq!(new(a, FieldElement::from(5), x, y, 1).err(), Some(CurveError::InvalidGeneratorOrder));
+
+	// The invalid parameters aren't registered
+	assert!(!Unregistered::is_registered());
+}
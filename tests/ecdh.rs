@@ -0,0 +1,57 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::crypto::ecdh::{ecdh, ecdh_hashed};
+use metamint::crypto::ecdsa::{SECP256R1, Secp256k1, Secp256r1, ECPoint, PrivateKey, PointError, Scalar, Secp256r1Scalar};
+use metamint::crypto::field::PrimeField;
+use metamint::crypto::hash::sha256;
+use metamint::utils::bignum::Uint256;
+use common::{unhex, bytes};
+
+fn key(seed: &[u8]) -> PrivateKey<Secp256k1> {
+	PrivateKey::new(Scalar::reduce(&Uint256::from_be_bytes(&sha256(seed)))).unwrap()
+}
+
+#[test]
+fn test_ecdh() {
+	let (alice, bob) = (key(b"alice"), key(b"bob"));
+
+	let secret = ecdh(&alice, bob.public_key().point()).unwrap();
+	assert_eq!(ecdh(&bob, alice.public_key().point()), Ok(secret.clone()));
+	assert_eq!(secret.to_bytes(), unhex("05aaea3882116920f603246a563cc2f3da5704bdf9d33ca60a29298956c26cf9"));
+
+	// SHA-256 of the compressed point
+	let hash = bytes("4e06de2520d1fe909bcf244b0a0de57c92bc6e21e28c2cdb108d980ad7d709b6");
+	assert_eq!(secret.hash(), hash);
+	assert_eq!(ecdh_hashed(&alice, bob.public_key().point()), Ok(hash));
+	assert_eq!(format!("{:?}", secret), "SharedSecret(..)");
+
+	assert!(ecdh(&alice, alice.public_key().point()).unwrap() != secret);
+}
+
+#[test]
+fn test_secp256r1() {
+	// NIST CAVS, ECC CDH primitive, P-256, COUNT = 0
+	let peer = SECP256R1.try_create_point(
+		Uint256::from_be_bytes(&bytes("700c48f77f56584c5cc632ca65640db91b6bacce3a4df6b42ce7cc838833d287")),
+		Uint256::from_be_bytes(&bytes("db71e509e3fd9b060ddb20ba5c51dcc5948d46fbf640dfe0441782cab85fa4ac"))
+	).unwrap();
+	let d = Secp256r1Scalar::from_bytes(&bytes("7d7dc5f71eb29ddaf80d6214632eeae03d9058af1fb6d22ed80badb62bc1a534")).unwrap();
+	let key = PrivateKey::<Secp256r1>::new(d).unwrap();
+
+	assert_eq!(
+		ecdh(&key, &peer).unwrap().to_bytes(),
+		unhex("46fc62106420ff012e54a434fbdd2d25ccc5852060561e68040dd7778997bd7b")
+	);
+}
+
+#[test]
+fn test_invalid_peer() {
+	let alice = key(b"alice");
+	let point = *key(b"bob").public_key().point();
+
+	assert_eq!(ecdh(&alice, &ECPoint::identity()), Err(PointError::PointAtInfinity));
+	assert_eq!(ecdh(&alice, &ECPoint::new(*point.x(), *point.x())), Err(PointError::NotOnCurve));
+	assert_eq!(ecdh_hashed(&alice, &ECPoint::new(*point.y(), *point.y())), Err(PointError::NotOnCurve));
+}
@@ -0,0 +1,308 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::crypto::ecdsa::{SECP256K1, Secp256k1, PrivateKey, PublicKey, Signature, RecoverableSignature, SignatureError, PointError, PointFormat, Scalar};
+use metamint::crypto::field::PrimeField;
+use metamint::utils::bignum::{Zero, One};
+use common::{unhex, bytes};
+
+fn scalar(hex: &str) -> Scalar {
+	Scalar::from_bytes(&bytes(hex)).unwrap()
+}
+
+/// (private key, sha256(message), nonce, r, s) for the secp256k1 curve.
+const VECTORS: [(&str, &str, &str, &str, &str); 3] = [
+	// sha256("Satoshi Nakamoto")
+	(
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e",
+		"8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+		"934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+		"dbbd3162d46e9f9bef7feb87c16dc13b4f6568a87f4e83f728e2443ba586675c"
+	),
+	// sha256("Satoshi Nakamoto")
+	(
+		"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+		"a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e",
+		"33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+		"fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d0",
+		"94c632f14e4379fc1ea610a3df5a375152549736425ee17cebe10abbc2a2826c"
+	),
+	// sha256("Alan Turing")
+	(
+		"f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+		"4ba38d48a60f1b29e9eb726eaff08b2e83d8d81e031666fee50e85900d7dc1ef",
+		"525a82b70e67874398067543fd84c83d30c175fdc45fdeee082fe13b1d7cfdf1",
+		"7063ae83e7f62bbb171798131b4a0564b956930092b33b07b395615d9ec7e15c",
+		"a72033e1ff5ca1ea8d0c99001cb45f0272d3be7525d3049c0d9e98dc7582b857"
+	)
+];
+
+#[test]
+fn test_private_key() {
+	assert!(PrivateKey::<Secp256k1>::new(Scalar::zero()).is_none());
+
+	let key = PrivateKey::<Secp256k1>::new(Scalar::one()).unwrap();
+	assert_eq!(*key.public_key().point(), SECP256K1.gen_point());
+	assert_eq!(format!("{:?}", key), "PrivateKey(..)");
+
+	let key = PrivateKey::<Secp256k1>::new(-Scalar::one()).unwrap();
+	assert_eq!(*key.public_key().point(), -SECP256K1.gen_point());
+}
+
+#[test]
+fn test_public_key() {
+	let g = SECP256K1.gen_point();
+
+	assert!(PublicKey::new(g).is_ok());
+	assert_eq!(PublicKey::new(SECP256K1.identity()), Err(PointError::PointAtInfinity));
+	assert_eq!(PublicKey::new(SECP256K1.create_point(*g.x(), *g.x())), Err(PointError::NotOnCurve));
+}
+
+#[test]
+fn test_public_key_encoding() {
+	// The public key of the "Alan Turing" vector
+	let key = PrivateKey::<Secp256k1>::new(scalar(VECTORS[2].0)).unwrap().public_key();
+	let compressed = key.encode(PointFormat::Compressed);
+
+	assert_eq!(&compressed[..], &unhex("0292df7b245b81aa637ab4e867c8d511008f79161a97d64f2ac709600352f7acbc")[..]);
+	assert_eq!(PublicKey::<Secp256k1>::decode(&compressed), Ok(key));
+	assert_eq!(PublicKey::<Secp256k1>::decode(&key.encode(PointFormat::Uncompressed)), Ok(key));
+	assert_eq!(PublicKey::<Secp256k1>::decode(&key.encode(PointFormat::Hybrid)), Err(PointError::HybridEncoding));
+	assert_eq!(PublicKey::<Secp256k1>::decode(&[0]), Err(PointError::PointAtInfinity));
+}
+
+#[test]
+fn test_signature() {
+	assert!(Signature::new(Scalar::zero(), Scalar::one()).is_none());
+	assert!(Signature::new(Scalar::one(), Scalar::zero()).is_none());
+
+	let sig = Signature::new(Scalar::one(), Scalar::from(2)).unwrap();
+	assert_eq!((*sig.r(), *sig.s()), (Scalar::one(), Scalar::from(2)));
+}
+
+#[test]
+fn test_sign_with_nonce() {
+	for &(d, hash, k, r, s) in VECTORS.iter() {
+		let key = PrivateKey::<Secp256k1>::new(scalar(d)).unwrap();
+		let sig = key.sign_with_nonce(&bytes(hash), &scalar(k)).unwrap();
+
+		assert_eq!(sig, Signature::new(scalar(r), scalar(s)).unwrap());
+	}
+
+	let key = PrivateKey::<Secp256k1>::new(Scalar::one()).unwrap();
+	assert!(key.sign_with_nonce(&[0; 32], &Scalar::zero()).is_none());
+}
+
+#[test]
+fn test_verify() {
+	for &(d, hash, k, _, _) in VECTORS.iter() {
+		let key = PrivateKey::<Secp256k1>::new(scalar(d)).unwrap();
+		let public = key.public_key();
+		let hash = bytes(hash);
+		let sig = key.sign_with_nonce(&hash, &scalar(k)).unwrap();
+
+		assert!(public.verify(&hash, &sig));
+
+		// (r, -s) is valid as well
+		assert!(public.verify(&hash, &Signature::new(*sig.r(), -*sig.s()).unwrap()));
+
+		let mut other_hash = hash;
+		other_hash[31] ^= 1;
+		assert!(!public.verify(&other_hash, &sig));
+		assert!(!public.verify(&hash, &Signature::new(*sig.r() + Scalar::one(), *sig.s()).unwrap()));
+		assert!(!public.verify(&hash, &Signature::new(*sig.r(), *sig.s() + Scalar::one()).unwrap()));
+
+		let other = PrivateKey::<Secp256k1>::new(scalar(d) * Scalar::from(2)).unwrap().public_key();
+		assert!(!other.verify(&hash, &sig));
+	}
+}
+
+#[test]
+fn test_sign_rfc6979() {
+	// The vectors above use the RFC 6979 nonces
+	for &(d, hash, _, r, s) in VECTORS.iter() {
+		let key = PrivateKey::<Secp256k1>::new(scalar(d)).unwrap();
+		assert_eq!(key.sign(&bytes(hash)), Signature::new(scalar(r), scalar(s)).unwrap());
+	}
+
+	// sha256("All those moments will be lost in time, like tears in rain. Time to die...")
+	let key = PrivateKey::<Secp256k1>::new(Scalar::one()).unwrap();
+	let hash = bytes("7d1833f54854ac51659521afcd0ec6dca2ce2351429614bfa28a756b1b3c637f");
+	let sig = key.sign(&hash);
+
+	assert_eq!(*sig.r(), scalar("8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b"));
+	assert_eq!(*sig.s(), scalar("ab8019bbd8b6924cc4099fe625340ffb1eaac34bf4477daa39d0835429094520"));
+	assert!(key.public_key().verify(&hash, &sig));
+
+	// sha256("There is a computer disease that anybody who works with computers knows about. ...")
+	let key = PrivateKey::<Secp256k1>::new(scalar("e91671c46231f833a6406ccbea0e3e392c76c167bac1cb013f6f1013980455c2")).unwrap();
+	let sig = key.sign(&bytes("1609a53bb33ef00e0cc1e784b436d7924956d87ec2b399574378312f07cba3e8"));
+
+	assert_eq!(*sig.r(), scalar("b552edd27580141f3b2a5463048cb7cd3e047b97c9f98076c32dbdf85a68718b"));
+	assert_eq!(*sig.s(), scalar("279fa72dd19bfae05577e06c7c0c1900c371fcd5893f7e1d56a37d30174671f6"));
+}
+
+#[test]
+fn test_sign_with_entropy() {
+	let key = PrivateKey::<Secp256k1>::new(Scalar::one()).unwrap();
+	let hash = bytes(VECTORS[0].1);
+	let mut entropy = [0u8; 32];
+	for (i, byte) in entropy.iter_mut().enumerate() {
+		*byte = i as u8;
+	}
+
+	let sig = key.sign_with_entropy(&hash, &entropy);
+	assert_eq!(*sig.r(), scalar("75ab07749ee08aaec97b52ac3e73c3b76cf0e5eed03929d64afecf6405799cac"));
+	assert_eq!(*sig.s(), scalar("53f78c086acf03f16dd417f94b453ddbaa9cb47b5afd524e8ad1352e9116c05d"));
+	assert!(key.public_key().verify(&hash, &sig));
+	assert_ne!(sig, key.sign(&hash));
+}
+
+#[test]
+fn test_low_s() {
+	let (_, _, _, r, s) = VECTORS[0];
+	let sig = Signature::new(scalar(r), scalar(s)).unwrap();
+	let low = sig.normalize_s();
+
+	assert!(!sig.is_low_s());
+	assert!(low.is_low_s());
+	assert_eq!(*low.s(), scalar("2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"));
+	assert_eq!(low.normalize_s(), low);
+
+	// n / 2 is the maximal low `s`
+	let half = scalar("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0");
+	assert!(Signature::new(Scalar::one(), half).unwrap().is_low_s());
+	assert!(!Signature::new(Scalar::one(), half + Scalar::one()).unwrap().is_low_s());
+
+	// The normalized signature is still valid
+	let key = PrivateKey::<Secp256k1>::new(scalar(VECTORS[0].0)).unwrap();
+	assert!(key.public_key().verify(&bytes(VECTORS[0].1), &low));
+}
+
+#[test]
+fn test_compact() {
+	let (_, _, _, r, s) = VECTORS[0];
+	let sig = Signature::new(scalar(r), scalar(s)).unwrap();
+	let compact = sig.to_compact();
+
+	assert_eq!(compact, unhex(&format!("{}{}", r, s)));
+	assert_eq!(Signature::from_compact(&compact), Ok(sig));
+	assert_eq!(Signature::<Scalar>::from_compact(&compact[1..]), Err(SignatureError::InvalidEncoding));
+	assert_eq!(Signature::<Scalar>::from_compact(&[0; 64]), Err(SignatureError::ComponentOutOfRange));
+	assert_eq!(Signature::<Scalar>::from_compact(&[0xff; 64]), Err(SignatureError::ComponentOutOfRange));
+}
+
+#[test]
+fn test_der() {
+	let (_, _, _, r, s) = VECTORS[0];
+	let sig = Signature::new(scalar(r), scalar(s)).unwrap();
+
+	// Both components have the high bit set
+	let der = unhex("3046022100934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8022100dbbd3162d46e9f9bef7feb87c16dc13b4f6568a87f4e83f728e2443ba586675c");
+	assert_eq!(sig.to_der(), der);
+	assert_eq!(Signature::from_der(&der), Ok(sig));
+
+	let der = unhex("3045022100934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d802202442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5");
+	assert_eq!(sig.normalize_s().to_der(), der);
+	assert_eq!(Signature::from_der(&der), Ok(sig.normalize_s()));
+
+	// Short components
+	let sig = Signature::new(Scalar::one(), Scalar::from(0x7f)).unwrap();
+	assert_eq!(sig.to_der(), unhex("300602010102017f"));
+	assert_eq!(Signature::from_der(&unhex("300602010102017f")), Ok(sig));
+
+	let sig = Signature::new(Scalar::from(0x80), Scalar::from(0x1234)).unwrap();
+	assert_eq!(sig.to_der(), unhex("30080202008002021234"));
+	assert_eq!(Signature::from_der(&unhex("30080202008002021234")), Ok(sig));
+}
+
+#[test]
+fn test_der_errors() {
+	let decode = |hex: &str| Signature::<Scalar>::from_der(&unhex(hex));
+
+	// Wrong structure
+	assert_eq!(decode(""), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("300602010102"), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("310602010102017f"), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("300702010102017f"), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("300603010102017f"), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("300602010103017f"), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("300602050102017f"), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("300602010102027f"), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("300602010102017f00"), Err(SignatureError::InvalidEncoding));
+	assert_eq!(decode("3006020002017f0000"), Err(SignatureError::InvalidEncoding));
+
+	// Not canonical: excess padding, negative numbers, long form of the length
+	assert_eq!(decode("30070202000102017f"), Err(SignatureError::NonCanonicalEncoding));
+	assert_eq!(decode("30070201010202007f"), Err(SignatureError::NonCanonicalEncoding));
+	assert_eq!(decode("300602018102017f"), Err(SignatureError::NonCanonicalEncoding));
+	assert_eq!(decode("300602010102018f"), Err(SignatureError::NonCanonicalEncoding));
+	assert_eq!(decode("30810602010102017f"), Err(SignatureError::NonCanonicalEncoding));
+
+	// Components out of the range
+	assert_eq!(decode("3006020100020101"), Err(SignatureError::ComponentOutOfRange));
+	assert_eq!(
+		decode("3026022100fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141020101"),
+		Err(SignatureError::ComponentOutOfRange)
+	);
+}
+
+#[test]
+fn test_sign_recoverable() {
+	for (&(d, hash, k, r, s), &recovery_id) in VECTORS.iter().zip([0, 1, 1].iter()) {
+		let key = PrivateKey::<Secp256k1>::new(scalar(d)).unwrap();
+		let hash = bytes(hash);
+		let sig = key.sign_recoverable_with_nonce(&hash, &scalar(k)).unwrap();
+
+		assert_eq!(*sig.signature(), Signature::new(scalar(r), scalar(s)).unwrap());
+		assert_eq!(sig.recovery_id(), recovery_id);
+		assert_eq!(key.sign_recoverable(&hash), sig);
+	}
+}
+
+#[test]
+fn test_recover() {
+	for &(d, hash, _, _, _) in VECTORS.iter() {
+		let key = PrivateKey::<Secp256k1>::new(scalar(d)).unwrap();
+		let hash = bytes(hash);
+		let sig = key.sign_recoverable(&hash);
+
+		assert_eq!(sig.recover::<Secp256k1>(&hash), Ok(key.public_key()));
+
+		// Other recovery ids and messages give other keys
+		let other = RecoverableSignature::new(*sig.signature(), sig.recovery_id() ^ 1).unwrap();
+		assert_ne!(other.recover::<Secp256k1>(&hash), Ok(key.public_key()));
+
+		let mut other_hash = hash;
+		other_hash[0] ^= 1;
+		assert_ne!(sig.recover::<Secp256k1>(&other_hash), Ok(key.public_key()));
+
+		// r + n is greater than p
+		let overflow = RecoverableSignature::new(*sig.signature(), sig.recovery_id() | 2).unwrap();
+		assert_eq!(overflow.recover::<Secp256k1>(&hash), Err(SignatureError::RecoveryFailed));
+	}
+
+	// x^3 + 7 isn't a square for x = 5
+	let sig = RecoverableSignature::new(Signature::new(Scalar::from(5), Scalar::one()).unwrap(), 0).unwrap();
+	assert_eq!(sig.recover::<Secp256k1>(&[0; 32]), Err(SignatureError::RecoveryFailed));
+}
+
+#[test]
+fn test_recoverable_compact() {
+	let key = PrivateKey::<Secp256k1>::new(scalar(VECTORS[1].0)).unwrap();
+	let sig = key.sign_recoverable(&bytes(VECTORS[1].1));
+	let compact = sig.to_compact();
+
+	assert_eq!(compact.len(), 65);
+	assert_eq!(compact[64], 1);
+	assert_eq!(RecoverableSignature::from_compact(&compact), Ok(sig));
+
+	let mut invalid = compact.clone();
+	invalid[64] = 4;
+	assert_eq!(RecoverableSignature::<Scalar>::from_compact(&invalid), Err(SignatureError::InvalidRecoveryId));
+	assert_eq!(RecoverableSignature::<Scalar>::from_compact(&compact[1..]), Err(SignatureError::InvalidEncoding));
+	assert_eq!(RecoverableSignature::<Scalar>::from_compact(&[]), Err(SignatureError::InvalidEncoding));
+	assert!(RecoverableSignature::new(*sig.signature(), 4).is_none());
+}
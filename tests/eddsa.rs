@@ -0,0 +1,153 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::crypto::eddsa::{EdwardsPoint, PrivateKey, PublicKey, Signature, Scalar, ED25519_L};
+use metamint::crypto::eddsa::{PointError, SignatureError};
+use metamint::crypto::field::PrimeField;
+use metamint::crypto::hash::sha512;
+use metamint::utils::bignum::Uint256;
+use common::{unhex, bytes};
+
+/// RFC 8032, section 7.1: the secret key, the public key, the message and the signature.
+const VECTORS: [(&str, &str, &str, &str); 3] = [
+	(
+		"9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+		"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+		"",
+		"e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+	),
+	(
+		"4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+		"3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+		"72",
+		"92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+	),
+	(
+		"c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+		"fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+		"af82",
+		"6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a"
+	)
+];
+
+#[test]
+fn test_vectors() {
+	for &(secret, public, msg, sig) in VECTORS.iter() {
+		let key = PrivateKey::from_bytes(&bytes(secret));
+		let msg = unhex(msg);
+
+		assert_eq!(key.public_key().to_bytes(), bytes(public));
+		assert_eq!(key.sign(&msg).to_bytes().to_vec(), unhex(sig));
+
+		let public = PublicKey::from_bytes(&bytes(public)).unwrap();
+		let sig = Signature::from_bytes(&bytes(sig)).unwrap();
+		assert!(public.verify(&msg, &sig));
+	}
+
+	// TEST SHA(abc)
+	let key = PrivateKey::from_bytes(&bytes("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42"));
+	let msg = sha512(b"abc");
+	let sig = key.sign(&msg);
+
+	assert_eq!(key.public_key().to_bytes(), bytes("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf"));
+	assert_eq!(
+		sig.to_bytes().to_vec(),
+		unhex("dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704")
+	);
+	assert!(key.public_key().verify(&msg, &sig));
+}
+
+#[test]
+fn test_verify_rejects() {
+	let (secret, public, _, sig) = VECTORS[1];
+	let key = PrivateKey::from_bytes(&bytes(secret));
+	let public = PublicKey::from_bytes(&bytes(public)).unwrap();
+	let sig = Signature::from_bytes(&bytes(sig)).unwrap();
+
+	// Another message and another key
+	assert!(!public.verify(&[0x73], &sig));
+	assert!(!PrivateKey::from_bytes(&[1; 32]).public_key().verify(&[0x72], &sig));
+	assert_eq!(key.public_key(), public);
+
+	// Changed R
+	let mut bytes = sig.to_bytes();
+	bytes[0] ^= 1;
+	assert!(!public.verify(&[0x72], &Signature::from_bytes(&bytes).unwrap()));
+
+	// S + L is the same scalar, but the encoding isn't canonical
+	let mut s = sig.s().to_uint() + ED25519_L;
+	let mut s_bytes = s.to_be_bytes();
+	s_bytes.reverse();
+	bytes = sig.to_bytes();
+	bytes[32..].copy_from_slice(&s_bytes);
+	assert_eq!(Signature::from_bytes(&bytes), Err(SignatureError::ScalarOutOfRange));
+
+	// S = L - 1 is the largest valid scalar
+	s = ED25519_L - Uint256::from(1);
+	s_bytes = s.to_be_bytes();
+	s_bytes.reverse();
+	bytes[32..].copy_from_slice(&s_bytes);
+	assert_eq!(*Signature::from_bytes(&bytes).unwrap().s(), -Scalar::from(1));
+}
+
+#[test]
+fn test_arithmetic() {
+	let b = EdwardsPoint::base_point();
+	let k = Scalar::from(0x1234_5678_9abc_def0);
+
+	assert_eq!(b + EdwardsPoint::identity(), b);
+	assert_eq!(b + b, b.double());
+	assert_eq!(b - b, EdwardsPoint::identity());
+	assert!((b - b).is_identity());
+	assert_eq!(b * k, b.mul_vartime(&k));
+	assert_eq!(b * -k, -(b * k));
+	assert_eq!(EdwardsPoint::mul_base(&(k + k)), (b * k).double());
+	assert_eq!(EdwardsPoint::double_mul(&k, &b, &k, &b), (b * k).double());
+	assert!((b * k).is_on_curve());
+	assert!((b * k).is_in_subgroup());
+}
+
+#[test]
+fn test_encoding() {
+	let b = EdwardsPoint::base_point();
+
+	// y = 4/5 = 0x6666...58, x is even
+	let mut encoded = [0x66u8; 32];
+	encoded[0] = 0x58;
+	assert_eq!(b.encode(), encoded);
+	assert_eq!(EdwardsPoint::decode(&encoded), Ok(b));
+	assert_eq!(EdwardsPoint::decode(&(-b).encode()), Ok(-b));
+
+	// y = p is out of the range
+	let mut p = [0xffu8; 32];
+	p[0] = 0xed;
+	p[31] = 0x7f;
+	assert_eq!(EdwardsPoint::decode(&p), Err(PointError::CoordinateOutOfRange));
+
+	// (y^2 - 1) / (dy^2 + 1) isn't a square for y = 2
+	let mut two = [0u8; 32];
+	two[0] = 2;
+	assert_eq!(EdwardsPoint::decode(&two), Err(PointError::NotOnCurve));
+
+	// The identity with the negative zero `x`
+	let mut identity = EdwardsPoint::identity().encode();
+	assert_eq!(EdwardsPoint::decode(&identity), Ok(EdwardsPoint::identity()));
+	identity[31] |= 0x80;
+	assert_eq!(EdwardsPoint::decode(&identity), Err(PointError::NegativeZero));
+}
+
+#[test]
+fn test_small_order() {
+	// (0, -1) has the order 2, it's on the curve, but not in the subgroup of the base point
+	let mut encoded = [0xffu8; 32];
+	encoded[0] = 0xec;
+	encoded[31] = 0x7f;
+	let point = EdwardsPoint::decode(&encoded).unwrap();
+
+	assert!(point.is_on_curve());
+	assert!(point.is_small_order());
+	assert!(!point.is_in_subgroup());
+	assert!(point.double().is_identity());
+
+}
@@ -0,0 +1,123 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::pow::{Equihash, EquihashError};
+use metamint::utils::bignum::Uint256;
+use common::{unhex, hex};
+
+/// Input of Zcash Equihash tests: the message followed by the 256-bit little-endian nonce.
+fn input(nonce: u8) -> Vec<u8> {
+	let mut res = b"Equihash is an asymmetric PoW based on the Generalised Birthday problem.".to_vec();
+	res.push(nonce);
+	res.extend_from_slice(&[0; 31]);
+	res
+}
+
+const SOLUTION_96_5: [u32; 32] = [
+	2261, 15185, 36112, 104243, 23779, 118390, 118332, 130041, 32642, 69878, 76925, 80080, 45858, 116805, 92842, 111026,
+	15972, 115059, 85191, 90330, 68190, 122819, 81830, 91132, 23460, 49807, 52426, 80391, 69567, 114474, 104973, 122568
+];
+
+#[test]
+fn test_params() {
+	assert_eq!(Equihash::zcash(), Equihash::new(200, 9).unwrap());
+	assert_eq!(Equihash::zcash().solution_size(), 1344);
+	assert_eq!(Equihash::new(96, 5).unwrap().solution_size(), 68);
+	assert_eq!(Equihash::new(48, 5).unwrap().solution_size(), 36);
+
+	assert_eq!(Equihash::new(200, 0), None);
+	assert_eq!(Equihash::new(100, 4), None);
+	assert_eq!(Equihash::new(96, 6), None);
+	assert_eq!(Equihash::new(520, 12), None);
+	assert_eq!(Equihash::new(64, 1), None);
+}
+
+#[test]
+fn test_encoding() {
+	let equihash = Equihash::new(96, 5).unwrap();
+	let solution = equihash.encode_solution(&SOLUTION_96_5).unwrap();
+	assert_eq!(
+		hex(&solution),
+		"046a8ed451a2197332e71f39db9c79fbf93fc1443da58fb38d0599172116d555b1b21f32705ce998f60da852f77f0e7f4d63fc2dd230a3d99953a0787dfefcab341bdec8"
+	);
+	assert_eq!(equihash.decode_solution(&solution), Some(SOLUTION_96_5.to_vec()));
+
+	assert_eq!(equihash.encode_solution(&SOLUTION_96_5[..16]), None);
+	assert_eq!(equihash.encode_solution(&[1 << 17; 32]), None);
+	assert_eq!(equihash.decode_solution(&solution[1..]), None);
+}
+
+#[test]
+fn test_verify() {
+	// Zcash test vector
+	let equihash = Equihash::new(96, 5).unwrap();
+	let solution = equihash.encode_solution(&SOLUTION_96_5).unwrap();
+	assert_eq!(equihash.verify(&input(1), &solution), Ok(()));
+	assert_eq!(equihash.verify(&input(2), &solution), Err(EquihashError::NoCollision));
+	assert_eq!(equihash.verify(&input(1), &solution[1..]), Err(EquihashError::InvalidLength));
+	assert_eq!(Equihash::new(48, 5).unwrap().verify(&input(1), &solution), Err(EquihashError::InvalidLength));
+
+	// Swapped subtrees still collide, but aren't ordered
+	let mut indices = SOLUTION_96_5.to_vec();
+	indices.swap(0, 1);
+	assert_eq!(equihash.verify(&input(1), &equihash.encode_solution(&indices).unwrap()), Err(EquihashError::InvalidOrder));
+
+	let mut indices = SOLUTION_96_5.to_vec();
+	let (left, right) = indices.split_at_mut(16);
+	left.swap_with_slice(right);
+	assert_eq!(equihash.verify(&input(1), &equihash.encode_solution(&indices).unwrap()), Err(EquihashError::InvalidOrder));
+
+	// Any changed index breaks the collisions
+	let mut indices = SOLUTION_96_5.to_vec();
+	indices[7] += 1;
+	assert_eq!(equihash.verify(&input(1), &equihash.encode_solution(&indices).unwrap()), Err(EquihashError::NoCollision));
+
+	let mut indices = SOLUTION_96_5.to_vec();
+	indices[1] = indices[0];
+	assert_eq!(equihash.verify(&input(1), &equihash.encode_solution(&indices).unwrap()), Err(EquihashError::DuplicateIndices));
+}
+
+#[test]
+fn test_solve() {
+	let equihash = Equihash::new(48, 5).unwrap();
+
+	let solutions = equihash.solve(&input(1));
+	assert_eq!(solutions.len(), 1);
+	assert_eq!(
+		equihash.decode_solution(&solutions[0]).unwrap(),
+		vec![
+			8, 229, 29, 139, 230, 351, 452, 507, 30, 374, 265, 424, 65, 90, 219, 502,
+			46, 255, 79, 262, 49, 51, 96, 203, 47, 182, 168, 366, 123, 411, 414, 504
+		]
+	);
+	assert_eq!(hex(&solutions[0]), "043943a8b7357f89fb0f5da13a820969b7f6173fc9f06188ccc0cb17ad9516e3de6f3df8");
+
+	let solutions = equihash.solve(&input(2));
+	assert_eq!(solutions, vec![unhex("16ea6cd6c2a976199331a2941684bb365f4c1eb1e4126347770183356f22f85527b5955d")]);
+	assert!(solutions.iter().all(|solution| equihash.verify(&input(2), solution).is_ok()));
+
+	assert!(equihash.solve(&input(3)).is_empty());
+
+	// The solver finds the known solution of the larger parameters too
+	let equihash = Equihash::new(96, 5).unwrap();
+	let solutions = equihash.solve(&input(1));
+	assert_eq!(solutions.len(), 2);
+	assert!(solutions.contains(&equihash.encode_solution(&SOLUTION_96_5).unwrap()));
+	assert!(solutions.iter().all(|solution| equihash.verify(&input(1), solution).is_ok()));
+}
+
+#[test]
+fn test_target() {
+	let equihash = Equihash::new(96, 5).unwrap();
+	let solution = equihash.encode_solution(&SOLUTION_96_5).unwrap();
+
+	let mut bytes = [0u8; 32];
+	bytes.copy_from_slice(&unhex("91a3ccab9c9e6b96cb4971f9f081b9ebd4eecd788ab3f29707b5a1af4b7e38a3"));
+	let hash = Uint256::from_be_bytes(&bytes);
+	assert_eq!(equihash.pow_hash(&input(1), &solution), hash);
+
+	assert!(equihash.meets_target(&input(1), &solution, &hash));
+	assert!(!equihash.meets_target(&input(1), &solution, &(hash - Uint256::from_u64(1))));
+	assert!(!equihash.meets_target(&input(2), &solution, &Uint256::max()));
+}
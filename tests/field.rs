@@ -0,0 +1,216 @@
+extern crate metamint;
+
+use metamint::crypto::ecdsa::{FieldElement, Scalar, Secp256r1FieldElement, Secp224k1Scalar, SECP256K1_P, SECP256K1_N};
+use metamint::crypto::field::{PrimeField, FieldModulus, Fp, is_prime_modulo};
+use metamint::utils::bignum::{Uint256, Zero, One};
+
+/// Fermat prime 2^16 + 1, p - 1 = 2^16 is the worst case for the Tonelli-Shanks algorithm.
+#[derive(Debug)]
+struct F65537;
+
+impl FieldModulus for F65537 {
+	const MODULO: Uint256 = Uint256([65537, 0, 0, 0]);
+}
+
+#[test]
+fn test_reduction() {
+	assert_eq!(FieldElement::new(SECP256K1_P), FieldElement::zero());
+	assert_eq!(Scalar::new(SECP256K1_N), Scalar::zero());
+	assert_eq!(Scalar::new(SECP256K1_N + Uint256::from(5)), Scalar::from(5));
+}
+
+#[test]
+fn test_from_uint() {
+	assert_eq!(FieldElement::from_uint(&SECP256K1_P), None);
+	assert_eq!(FieldElement::from_uint(&(SECP256K1_P - Uint256::one())), Some(-FieldElement::one()));
+	assert_eq!(Scalar::from_uint(&Uint256::from(5)).map(|s| s.to_uint()), Some(Uint256::from(5)));
+	assert_eq!(Scalar::reduce(&(SECP256K1_N + Uint256::from(5))), Scalar::from(5));
+}
+
+#[test]
+fn test_bytes() {
+	let a = FieldElement::new(Uint256::from_raw([0xaa7f067e28fef8ac, 0xaa2f64be71462131, 0x42fd096d2f1f7fd9, 0x8ff2b776aaf6d919]));
+	let bytes = a.to_bytes();
+
+	assert_eq!(&bytes[..8], &[0x8f, 0xf2, 0xb7, 0x76, 0xaa, 0xf6, 0xd9, 0x19]);
+	assert_eq!(&bytes[24..], &[0xaa, 0x7f, 0x06, 0x7e, 0x28, 0xfe, 0xf8, 0xac]);
+	assert_eq!(FieldElement::from_bytes(&bytes), Some(a));
+	assert_eq!(FieldElement::from_bytes(&[0xff; 32]), None);
+}
+
+#[test]
+fn test_add() {
+	// 0x ffffffffffffffff ffffffffffffffff ffffffffffffffff fffffffeaffffc2f
+	// +
+	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a89c42b584
+	// =
+	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a84c42b584
+	// mod p
+
+	assert_eq!(
+		FieldElement::new(Uint256::from_raw([0xfffffffeaffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]))
+			+ FieldElement::new(Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f])),
+		FieldElement::new(Uint256::from_raw([0xbbf030a84c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]))
+	);
+
+	// (p - 1) + 1 = 0 (mod p)
+	assert!((-FieldElement::one() + FieldElement::one()).is_zero());
+	assert!((-Scalar::one() + Scalar::one()).is_zero());
+}
+
+#[test]
+fn test_sub() {
+	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a89c42b584
+	// -
+	// 0x ffffffffffffffff ffffffffffffffff ffffffffffffffff fffffffeaffffc2f
+	// =
+	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a8ec42b584
+	// mod p
+
+	assert_eq!(
+		FieldElement::new(Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]))
+			- FieldElement::new(Uint256::from_raw([0xfffffffeaffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff])),
+		FieldElement::new(Uint256::from_raw([0xbbf030a8ec42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]))
+	);
+}
+
+#[test]
+fn test_mul() {
+	// 0x 162ebcd38c90b56f bdb4b0390695afb4 71c944a6003cb334 bbf030a89c42b584
+	// *
+	// 0x ffffffffffffffff ffffffffffffffff ffffffffffffffff fffffffeaffffc2f
+	// =
+	// 0x e412c74d14b788ee 2df139179c711a8c 1fed07ff8544f0cb 483c8c294b62698d
+	// mod p
+
+	assert_eq!(
+		FieldElement::new(Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]))
+			* FieldElement::new(Uint256::from_raw([0xfffffffeaffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff])),
+		FieldElement::new(Uint256::from_raw([0x483c8c294b62698d, 0x1fed07ff8544f0cb, 0x2df139179c711a8c, 0xe412c74d14b788ee]))
+	);
+}
+
+#[test]
+fn test_pow() {
+	let a = FieldElement::from(3);
+
+	assert_eq!(a.pow(&Uint256::zero()), FieldElement::one());
+	assert_eq!(a.pow(&Uint256::from(5)), FieldElement::from(243));
+
+	// a^(p - 1) = 1 (mod p)
+	assert_eq!(a.pow(&(SECP256K1_P - Uint256::one())), FieldElement::one());
+}
+
+#[test]
+fn test_neg() {
+	let a = FieldElement::new(Uint256::from_raw([0xaa7f067e28fef8ac, 0xaa2f64be71462131, 0x42fd096d2f1f7fd9, 0x8ff2b776aaf6d919]));
+
+	assert_eq!(-a, FieldElement::new(Uint256::from_raw([0x5580f980d7010383, 0x55d09b418eb9dece, 0xbd02f692d0e08026, 0x700d4889550926e6])));
+	assert_eq!(-a + a, FieldElement::zero());
+	assert_eq!(-FieldElement::zero(), FieldElement::zero());
+}
+
+#[test]
+fn test_div() {
+	let a = FieldElement::new(Uint256::from_raw([0xaa7f067e28fef8ac, 0xaa2f64be71462131, 0x42fd096d2f1f7fd9, 0x8ff2b776aaf6d919]));
+	let b = FieldElement::new(Uint256::from_raw([0x388fa11ff621a970, 0xfa2f68914d0aa833, 0xbb49f7f81c221151, 0x9075b4ee4d4788ca]));
+
+	assert_eq!(a / b, FieldElement::new(Uint256::from_raw([0x36d3aebbeddcd1b1, 0xf58857c2f631ee69, 0x3eb9d1235992ac63, 0xcb35b28428101a30])));
+}
+
+#[test]
+fn test_invert() {
+	let b = FieldElement::new(Uint256::from_raw([0x388fa11ff621a970, 0xfa2f68914d0aa833, 0xbb49f7f81c221151, 0x9075b4ee4d4788ca]));
+	let inv = b.invert().unwrap();
+
+	assert_eq!(inv, FieldElement::new(Uint256::from_raw([0x0fbeaeec9767a6a6, 0xc155602353dc3d34, 0x4de79011c5f0a46a, 0xb7e31a064ed74d31])));
+	assert!((b * inv).is_one());
+	assert_eq!(FieldElement::zero().invert(), None);
+
+	// 0x 45b0c38fa5476635 4cf3409d38b87325 5dfa9ed3407a542b a48eb9cab9dfca67
+	let k = Scalar::new(Uint256::from_raw([0xa48eb9cab9dfca67, 0x5dfa9ed3407a542b, 0x4cf3409d38b87325, 0x45b0c38fa5476635]));
+
+	assert_eq!(k.invert(), Some(Scalar::new(Uint256::from_raw([0x5cd23b0348004505, 0x34a4640b8882fbfd, 0x771c3e82fedf3e4b, 0xc26eef59fe429b1a]))));
+	assert_eq!(Scalar::zero().invert(), None);
+}
+
+#[test]
+fn test_sqrt() {
+	let x = FieldElement::new(Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]));
+	let root = x.square().sqrt().unwrap();
+
+	assert_eq!(x.square(), FieldElement::new(Uint256::from_raw([0x1bbef65872a54569, 0x458fb6a2b318e848, 0xd3f53379a0bdc6f3, 0xe0e540a383e09702])));
+	assert!(root == x || root == -x);
+
+	// 3 and 7 are not quadratic residues modulo p
+	assert!(!FieldElement::from(3).is_quadratic_residue());
+	assert!(FieldElement::from(4).is_quadratic_residue());
+	assert_eq!(FieldElement::from(3).sqrt(), None);
+	assert_eq!(FieldElement::from(7).sqrt(), None);
+	assert_eq!(FieldElement::zero().sqrt(), Some(FieldElement::zero()));
+
+	// n = 1 (mod 4), so the scalar root is found by the Tonelli-Shanks algorithm
+	let k = Scalar::new(Uint256::from_raw([0xa48eb9cab9dfca67, 0x5dfa9ed3407a542b, 0x4cf3409d38b87325, 0x45b0c38fa5476635]));
+	let root = k.square().sqrt().unwrap();
+
+	assert_eq!(k.square(), Scalar::new(Uint256::from_raw([0x3924dfaa6939d207, 0x42389d1e72783a1a, 0x91a7341dbdb9a77c, 0x500f43404c9e1f02])));
+	assert!(root == k || root == -k);
+}
+
+#[test]
+fn test_custom_modulo() {
+	type F = Fp<F65537>;
+
+	assert_eq!(F::NUM_BITS, 17);
+	assert_eq!(F::from(65536) + F::from(2), F::one());
+	assert_eq!(F::from(3).invert().unwrap() * F::from(3), F::one());
+
+	// 3 is a primitive root modulo 65537
+	assert!(!F::from(3).is_quadratic_residue());
+	assert_eq!(F::from(3).sqrt(), None);
+
+	for i in 1..50 {
+		let a = F::from(i * 1237);
+		let root = a.square().sqrt().unwrap();
+		assert!(root == a || root == -a);
+	}
+}
+
+#[test]
+fn test_is_prime_modulo() {
+	macro_rules! modulo {
+		($name:ident, $value:expr) => {
+			#[derive(Debug)]
+			struct $name;
+
+			impl FieldModulus for $name {
+				const MODULO: Uint256 = $value;
+			}
+		}
+	}
+
+	// Carmichael number, strong pseudoprime to the bases 2, 3, 5 and 7, 2^256 - 1
+	modulo!(Carmichael, Uint256([561, 0, 0, 0]));
+	modulo!(Pseudoprime, Uint256([3215031751, 0, 0, 0]));
+	modulo!(Mersenne, Uint256([0xffffffffffffffff; 4]));
+	modulo!(Unit, Uint256([1, 0, 0, 0]));
+
+	// The first 12 primes are enough for the moduli less than 2^81
+	let bases: Vec<Uint256> = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37].iter().map(|&b| Uint256::from(b)).collect();
+
+	assert!(is_prime_modulo::<FieldElement>(&bases));
+	assert!(is_prime_modulo::<Scalar>(&bases));
+	assert!(is_prime_modulo::<Secp256r1FieldElement>(&bases));
+	assert!(is_prime_modulo::<Secp224k1Scalar>(&bases));
+	assert!(is_prime_modulo::<Fp<F65537>>(&bases));
+
+	assert!(!is_prime_modulo::<Fp<Carmichael>>(&bases));
+	assert!(!is_prime_modulo::<Fp<Pseudoprime>>(&bases));
+	assert!(is_prime_modulo::<Fp<Pseudoprime>>(&bases[..4]));
+	assert!(!is_prime_modulo::<Fp<Mersenne>>(&bases));
+	assert!(!is_prime_modulo::<Fp<Unit>>(&bases));
+
+	// The test without the bases proves nothing, so even the prime moduli are rejected
+	assert!(!is_prime_modulo::<Fp<Carmichael>>(&[]));
+	assert!(!is_prime_modulo::<FieldElement>(&[]));
+}
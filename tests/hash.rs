@@ -0,0 +1,360 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::crypto::hash::{Hasher, Sha256, Sha256d, sha256, sha256d, sha256d_uint, Sha512, sha512};
+use metamint::crypto::hash::{Ripemd160, Hash160, ripemd160, hash160};
+use metamint::crypto::hash::{Keccak256, Sha3_256, keccak256, sha3_256, Blake2b, Blake2b256, Blake2b512, blake2b256, blake2b512};
+use metamint::crypto::hash::{Hmac, HmacSha256, HmacSha512, hmac, hmac_sha256, hmac_sha512, hkdf, hkdf_extract, hkdf_expand, pbkdf2, scrypt};
+use metamint::utils::bignum::Uint256;
+use common::{unhex, hex};
+
+#[test]
+fn test_sha256() {
+	// FIPS 180-2 examples
+	assert_eq!(hex(&sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+	assert_eq!(hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+	assert_eq!(
+		hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+		"248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+	);
+	assert_eq!(hex(&sha256(&[b'a'; 1_000_000])), "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0");
+}
+
+#[test]
+fn test_sha256_streaming() {
+	let data: Vec<u8> = (0..768).map(|i| i as u8).collect();
+
+	// Parts of the different lengths cross the block bounds
+	for &part in [1, 7, 55, 56, 63, 64, 65, 200].iter() {
+		let mut hasher = Sha256::new();
+		for chunk in data.chunks(part) {
+			hasher.update(chunk);
+		}
+
+		assert_eq!(hex(&hasher.finalize()), "f3a25aa93aa2fbba28d79260535bbd6a5eb0fc1c24a8b0f04e12b484c1dfe363");
+	}
+}
+
+#[test]
+fn test_sha512() {
+	// FIPS 180-2 examples
+	assert_eq!(
+		hex(&sha512(b"")),
+		"cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+	);
+	assert_eq!(
+		hex(&sha512(b"abc")),
+		"ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+	);
+	assert_eq!(
+		hex(&sha512(b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu")),
+		"8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"
+	);
+	assert_eq!(
+		hex(&sha512(&[b'a'; 1_000_000])),
+		"e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b"
+	);
+}
+
+#[test]
+fn test_sha512_streaming() {
+	let data: Vec<u8> = (0..768).map(|i| i as u8).collect();
+
+	// Parts of the different lengths cross the block bounds
+	for &part in [1, 7, 111, 112, 127, 128, 129, 300].iter() {
+		let mut hasher = Sha512::new();
+		for chunk in data.chunks(part) {
+			hasher.update(chunk);
+		}
+
+		assert_eq!(
+			hex(&hasher.finalize()),
+			"f1721f49518ee462a3d81def26d81037cd474b4254b85ad7c8f1509594d0177bbb996ee9625813852bacac108c2a72c83a8587050fec1dcda64730d6470953e6"
+		);
+	}
+}
+
+#[test]
+fn test_sha256d() {
+	assert_eq!(hex(&sha256d(b"hello")), "9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50");
+	assert_eq!(sha256d(b"abc"), sha256(&sha256(b"abc")));
+
+	let mut hasher = Sha256d::new();
+	hasher.update(b"hel");
+	hasher.update(b"lo");
+	assert_eq!(hasher.finalize(), Sha256d::digest(b"hello"));
+
+	// The header of the Bitcoin genesis block
+	let header = unhex(
+		"0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f61\
+		7fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c"
+	);
+	assert_eq!(
+		sha256d_uint(&header),
+		Uint256::from_raw([0x72b3f1b60a8ce26f, 0x4ff763ae46a2a6c1, 0x9c085ae165831e93, 0x000000000019d668])
+	);
+	assert_eq!(format!("{:?}", sha256d_uint(&header)), "0x000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f");
+}
+
+#[test]
+fn test_ripemd160() {
+	// Test vectors from the RIPEMD-160 specification
+	assert_eq!(hex(&ripemd160(b"")), "9c1185a5c5e9fc54612808977ee8f548b2258d31");
+	assert_eq!(hex(&ripemd160(b"a")), "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe");
+	assert_eq!(hex(&ripemd160(b"abc")), "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+	assert_eq!(hex(&ripemd160(b"message digest")), "5d0689ef49d2fae572b881b123a85ffa21595f36");
+	assert_eq!(
+		hex(&ripemd160(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890")),
+		"9b752e45573d4b39f4dbd3323cab82bf63326bfb"
+	);
+	assert_eq!(hex(&ripemd160(&[b'a'; 1_000_000])), "52783243c1697bdbe16d37f97f68f08325dc1528");
+
+	let data: Vec<u8> = (0..768).map(|i| i as u8).collect();
+	for &part in [1, 7, 55, 56, 63, 64, 65, 200].iter() {
+		let mut hasher = Ripemd160::new();
+		for chunk in data.chunks(part) {
+			hasher.update(chunk);
+		}
+
+		assert_eq!(hex(&hasher.finalize()), "0f1cac3e40e9cec107e875816e711ade87911b63");
+	}
+}
+
+#[test]
+fn test_hash160() {
+	// The compressed public key of the private key 1
+	let key = unhex("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+	assert_eq!(hex(&hash160(&key)), "751e76e8199196d454941c45d1b3a323f1433bd6");
+	assert_eq!(Hash160::digest(&key), hash160(&key));
+	assert_eq!(<Hash160 as Hasher>::DIGEST_SIZE, 20);
+}
+
+#[test]
+fn test_hmac_sha256() {
+	// RFC 4231 test cases 1, 2 and 6
+	assert_eq!(hex(&hmac_sha256(&[0x0b; 20], b"Hi There")), "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7");
+	assert_eq!(hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+
+	let mut hmac = HmacSha256::new(&[0xaa; 131]);
+	hmac.update(b"Test Using Larger Than Block-Size Key - ");
+	hmac.update(b"Hash Key First");
+	assert_eq!(hex(&hmac.finalize()), "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54");
+}
+
+#[test]
+fn test_hmac_sha512() {
+	// RFC 4231 test cases 1, 2, 6 and 7
+	assert_eq!(
+		hex(&hmac_sha512(&[0x0b; 20], b"Hi There")),
+		"87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+	);
+	assert_eq!(
+		hex(&hmac_sha512(b"Jefe", b"what do ya want for nothing?")),
+		"164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+	);
+	assert_eq!(
+		hex(&hmac_sha512(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First")),
+		"80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+	);
+
+	let data: &[u8] = b"This is a test using a larger than block-size key and a larger than block-size data. \
+		The key needs to be hashed before being used by the HMAC algorithm.";
+	assert_eq!(
+		hex(&hmac::<Sha512>(&[0xaa; 131], data)),
+		"e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58"
+	);
+	assert_eq!(hex(&hmac::<Sha256>(&[0xaa; 131], data)), "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2");
+
+	let mut hmac = HmacSha512::new(b"Jefe");
+	hmac.update(b"what do ya want ");
+	hmac.update(b"for nothing?");
+	assert_eq!(hmac.finalize(), hmac_sha512(b"Jefe", b"what do ya want for nothing?"));
+}
+
+#[test]
+fn test_hmac_generic() {
+	// RFC 2286 test case 1: HMAC over RIPEMD-160
+	let mut hmac_ripemd = Hmac::<Ripemd160>::new(&[0x0b; 20]);
+	hmac_ripemd.update(b"Hi There");
+	assert_eq!(hex(&hmac_ripemd.finalize()), "24cb4bd67d20fc1a5d2ed7732dcc39377f0a5668");
+
+	// The inner and the outer hashes are double SHA-256
+	assert!(hmac::<Sha256d>(b"key", b"") != hmac::<Sha256>(b"key", b""));
+}
+
+#[test]
+fn test_hkdf() {
+	// RFC 5869 test case 1
+	let ikm = [0x0b; 22];
+	let salt: Vec<u8> = (0x00..0x0d).collect();
+	let info: Vec<u8> = (0xf0..0xfa).collect();
+	let prk = hkdf_extract::<Sha256>(&salt, &ikm);
+	assert_eq!(hex(&prk), "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5");
+	assert_eq!(
+		hex(&hkdf_expand::<Sha256>(&prk, &info, 42).unwrap()),
+		"3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+	);
+
+	// RFC 5869 test case 2: the long inputs
+	let ikm: Vec<u8> = (0x00..0x50).collect();
+	let salt: Vec<u8> = (0x60..0xb0).collect();
+	let info: Vec<u8> = (0xb0..=0xff).collect();
+	assert_eq!(hex(&hkdf_extract::<Sha256>(&salt, &ikm)), "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244");
+	assert_eq!(
+		hex(&hkdf::<Sha256>(&salt, &ikm, &info, 82).unwrap()),
+		"b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71cc30c58179ec3e87c14c01d5c1f3434f1d87"
+	);
+
+	// RFC 5869 test case 3: the empty salt and info
+	let ikm = [0x0b; 22];
+	assert_eq!(hex(&hkdf_extract::<Sha256>(&[], &ikm)), "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04");
+	assert_eq!(
+		hex(&hkdf::<Sha256>(&[], &ikm, &[], 42).unwrap()),
+		"8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+	);
+
+	// The output is limited by 255 blocks
+	assert_eq!(hkdf::<Sha256>(&[], &ikm, &[], 255 * 32).map(|okm| okm.len()), Some(255 * 32));
+	assert_eq!(hkdf::<Sha256>(&[], &ikm, &[], 255 * 32 + 1), None);
+}
+
+#[test]
+fn test_pbkdf2() {
+	// The inputs of RFC 6070 with HMAC-SHA256
+	assert_eq!(hex(&pbkdf2::<Sha256>(b"password", b"salt", 1, 32)), "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b");
+	assert_eq!(hex(&pbkdf2::<Sha256>(b"password", b"salt", 2, 32)), "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43");
+	assert_eq!(hex(&pbkdf2::<Sha256>(b"password", b"salt", 4096, 32)), "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a");
+	assert_eq!(
+		hex(&pbkdf2::<Sha256>(b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 40)),
+		"348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9"
+	);
+	assert_eq!(hex(&pbkdf2::<Sha256>(b"pass\0word", b"sa\0lt", 4096, 16)), "89b69d0516f829893c696226650a8687");
+
+	// RFC 7914, section 11
+	assert_eq!(
+		hex(&pbkdf2::<Sha256>(b"passwd", b"salt", 1, 64)),
+		"55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+	);
+
+	assert_eq!(
+		hex(&pbkdf2::<Sha512>(b"password", b"salt", 2, 64)),
+		"e1d9c16aa681708a45f5c7c4e215ceb66e011a2e9f0040713f18aefdb866d53cf76cab2868a39b9f7840edce4fef5a82be67335c77a6068e04112754f27ccf4e"
+	);
+}
+
+#[test]
+fn test_scrypt() {
+	// RFC 7914, section 12
+	assert_eq!(
+		hex(&scrypt(b"", b"", 16, 1, 1, 64)),
+		"77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"
+	);
+	assert_eq!(
+		hex(&scrypt(b"password", b"NaCl", 1024, 8, 16, 64)),
+		"fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+	);
+}
+
+#[test]
+fn test_keccak256() {
+	assert_eq!(hex(&keccak256(b"")), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+	assert_eq!(hex(&keccak256(b"abc")), "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45");
+	assert_eq!(hex(&keccak256(b"hello")), "1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8");
+
+	// The identifiers are the big-endian numbers
+	assert_eq!(
+		Keccak256::digest_uint(b"hello"),
+		Uint256::from_raw([0x319a09a7a36deac8, 0x56d9517b9c948127, 0x4bc3174f3472287b, 0x1c8aff950685c2ed])
+	);
+
+	let data: Vec<u8> = (0..768).map(|i| i as u8).collect();
+	for &part in [1, 7, 135, 136, 137, 300].iter() {
+		let mut hasher = Keccak256::new();
+		for chunk in data.chunks(part) {
+			hasher.update(chunk);
+		}
+
+		assert_eq!(hex(&hasher.finalize()), "00e77ce2c4f77212a0d5df106b08157b77058479357a98a6039b457c469723e4");
+	}
+}
+
+#[test]
+fn test_sha3_256() {
+	// FIPS 202 examples
+	assert_eq!(hex(&sha3_256(b"")), "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a");
+	assert_eq!(hex(&sha3_256(b"abc")), "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+	assert_eq!(
+		hex(&sha3_256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+		"41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376"
+	);
+	assert_eq!(hex(&sha3_256(&[b'a'; 1_000_000])), "5c8875ae474a3634ba4fd55ec85bffd661f32aca75c6d699d0cdcb6c115891c1");
+
+	let data: Vec<u8> = (0..768).map(|i| i as u8).collect();
+	let mut hasher = Sha3_256::new();
+	for chunk in data.chunks(100) {
+		hasher.update(chunk);
+	}
+	assert_eq!(hex(&hasher.finalize()), "c043b2b15d405c9f4cd92fdaef420eba6201d328fb34ec0e2c16e4981b9e4b39");
+}
+
+#[test]
+fn test_blake2b() {
+	// RFC 7693, appendix A
+	assert_eq!(
+		hex(&blake2b512(b"abc")),
+		"ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+	);
+	assert_eq!(
+		hex(&Blake2b512::digest(b"")),
+		"786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+	);
+	assert_eq!(hex(&blake2b256(b"abc")), "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319");
+
+	// The last full block is processed as the final one
+	assert_eq!(hex(&blake2b256(&[0; 128])), "378d0caaaa3855f1b38693c1d6ef004fd118691c95c959d4efa950d6d6fcf7c1");
+	assert_eq!(hex(&blake2b256(&[0; 129])), "baadfb64c3bd2cd187b54accc5e61a0720ed86bf48c28017873536cf9015d1b8");
+
+	let data: Vec<u8> = (0..768).map(|i| i as u8).collect();
+	for &part in [1, 7, 127, 128, 129, 300].iter() {
+		let mut hasher = Blake2b256::new();
+		for chunk in data.chunks(part) {
+			hasher.update(chunk);
+		}
+
+		assert_eq!(hex(&hasher.finalize()), "b8007121274217790e2923e0ad7027986e5a99d5531ef6ae7d294140fc81615d");
+	}
+}
+
+#[test]
+fn test_blake2b_params() {
+	// The keyed test vectors of the BLAKE2 reference implementation
+	let key: Vec<u8> = (0..64).collect();
+	let hasher = Blake2b::with_params(64, &key, &[0; 16]);
+	assert_eq!(
+		hex(&hasher.finalize()),
+		"10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568"
+	);
+
+	let data: Vec<u8> = (0..=255).collect();
+	let mut hasher = Blake2b::with_params(64, &key, &[0; 16]);
+	hasher.update(&data);
+	assert_eq!(
+		hex(&hasher.finalize()),
+		"b72071e096277edebb8ee5134dd3714996307ba3a55aa4733d412abbe28e909e10e57e6fbfb4ef53b3b960518294ff889a90829254412e2a60b85add07a3674f"
+	);
+
+	// The personalization of Equihash (200, 9) and the digest of 50 bytes
+	let mut personal = [0u8; 16];
+	personal[..8].copy_from_slice(b"ZcashPoW");
+	personal[8..12].copy_from_slice(&200u32.to_le_bytes());
+	personal[12..].copy_from_slice(&9u32.to_le_bytes());
+
+	let mut hasher = Blake2b::with_params(50, &[], &personal);
+	hasher.update(b"abc");
+	assert_eq!(hasher.digest_len(), 50);
+	assert_eq!(
+		hex(&hasher.finalize()),
+		"52e907446f88b0d5e63e3b2ed93b9cf178cff963d9b89e2a01fe2e42f247b0a58f8f40ccd4471fdadee85d6ab7e69be29285"
+	);
+}
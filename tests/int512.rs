@@ -7,6 +7,15 @@ fn test_zero() {
 	assert_eq!(Int512::zero(), Int512::from_raw(Uint512::zero(), true));
 }
 
+#[test]
+fn test_from_i64() {
+	assert_eq!(Int512::from(-5i64), Int512::from_raw(Uint512::from(5u64), false));
+	assert_eq!(Int512::from(1i64 << 40), Int512::from_raw(Uint512::from(1u64 << 40), true));
+	assert_eq!(Int512::from(-(1i64 << 40)), Int512::from_raw(Uint512::from(1u64 << 40), false));
+	assert_eq!(Int512::from(i64::MAX), Int512::from_raw(Uint512::from(i64::MAX as u64), true));
+	assert_eq!(Int512::from(i64::MIN), Int512::from_raw(Uint512::from(1u64 << 63), false));
+}
+
 #[test]
 fn test_eq() {
 	let a = Int512::from_raw(Uint512::from_raw([0x5340d92f6c5155f3, 0x2a2db477db2a939a, 0x2a5f6398a0e1fe86, 0xff954daf3f2c2cf3, 0xef8fc610c5603195, 0x2c908de78a593ba0, 0x59f24e98fb556bd1, 0x752fc6c82740ec88]), true);
@@ -20,6 +29,7 @@ fn test_eq() {
 }
 
 #[test]
+#[allow(clippy::nonminimal_bool)]
 fn test_ord() {
 	// [+] 0x 752fc6c82740ec88 59f24e98fb556bd1 2c908de78a593ba0 ef8fc610c5603195 ff954daf3f2c2cf3 2a5f6398a0e1fe86 2a2db477db2a939a 5340d92f6c5155f3
 	let a = Int512::from_raw(Uint512::from_raw([0x5340d92f6c5155f3, 0x2a2db477db2a939a, 0x2a5f6398a0e1fe86, 0xff954daf3f2c2cf3, 0xef8fc610c5603195, 0x2c908de78a593ba0, 0x59f24e98fb556bd1, 0x752fc6c82740ec88]), true);
@@ -0,0 +1,65 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::pow::{PowHasher, Sha256dPow, ScryptPow, HEADER_SIZE};
+use metamint::utils::bignum::Uint256;
+use common::unhex;
+
+fn uint(hex: &str) -> Uint256 {
+	let mut bytes = [0u8; 32];
+	bytes.copy_from_slice(&unhex(hex));
+	Uint256::from_be_bytes(&bytes)
+}
+
+/// Litecoin genesis block header.
+fn litecoin_genesis() -> [u8; HEADER_SIZE] {
+	let mut header = [0u8; HEADER_SIZE];
+	header.copy_from_slice(&unhex(
+		"0100000000000000000000000000000000000000000000000000000000000000000000\
+		00d9ced4ed1130f7b7faad9be25323ffafa33232a17c3edf6cfd97bee6bafbdd97b9aa8e4ef0ff0f1ecd513f7c"
+	));
+	header
+}
+
+#[test]
+fn test_sha256d_pow() {
+	// The block hash of Litecoin genesis is the double SHA-256 of the header
+	let header = litecoin_genesis();
+	let hash = uint("12a765e31ffd4059bada1e25190f6e98c99d9714d334efa41a195a7e7e04bfe2");
+	assert_eq!(Sha256dPow.hash(&header), hash);
+	assert!(Sha256dPow.meets_target(&header, &hash));
+	assert!(!Sha256dPow.meets_target(&header, &(hash - Uint256::from_u64(1))));
+}
+
+#[test]
+fn test_scrypt_pow() {
+	let header = litecoin_genesis();
+	let pow = ScryptPow::litecoin();
+	assert_eq!((pow.n(), pow.r(), pow.p()), (1024, 1, 1));
+	assert_eq!(pow, ScryptPow::default());
+	assert_eq!(pow.hash(&header), uint("0000050c34a64b415b6b15b37f2216634b5b1669cb9a2e38d76f7213b0671e00"));
+
+	// Compact target 0x1e0ffff0 of Litecoin genesis
+	let target = uint("00000ffff0000000000000000000000000000000000000000000000000000000");
+	assert!(pow.meets_target(&header, &target));
+	assert!(!pow.meets_target(&header, &uint("000004ffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")));
+
+	// The chain parameters can keep any algorithm behind the trait object
+	let algorithms: Vec<Box<dyn PowHasher>> = vec![Box::new(Sha256dPow), Box::new(pow)];
+	assert!(algorithms.iter().all(|algo| algo.meets_target(&header, &Uint256::max())));
+	assert!(!algorithms[0].meets_target(&header, &target));
+	assert!(algorithms[1].meets_target(&header, &target));
+}
+
+#[test]
+fn test_scrypt_params() {
+	assert!(ScryptPow::new(1024, 1, 1).is_some());
+	assert!(ScryptPow::new(16, 8, 16).is_some());
+	assert!(ScryptPow::new(0, 1, 1).is_none());
+	assert!(ScryptPow::new(1, 1, 1).is_none());
+	assert!(ScryptPow::new(1000, 1, 1).is_none());
+	assert!(ScryptPow::new(1024, 0, 1).is_none());
+	assert!(ScryptPow::new(1024, 1, 0).is_none());
+	assert!(ScryptPow::new(1024, 1 << 15, 1 << 15).is_none());
+}
@@ -0,0 +1,134 @@
+extern crate metamint;
+
+mod common;
+
+use metamint::crypto::schnorr::{XOnlyPublicKey, SchnorrSignature, tagged_hash, schnorr_sign, schnorr_verify, schnorr_verify_batch};
+use metamint::crypto::ecdsa::{Secp256k1, PrivateKey, PointError, SignatureError, Scalar};
+use metamint::crypto::field::PrimeField;
+use metamint::crypto::hash::sha256;
+use metamint::utils::bignum::Uint256;
+use common::{unhex, bytes};
+
+/// Official BIP340 test vectors.
+const VECTORS: &str = include_str!("data/bip340_vectors.csv");
+
+struct Vector {
+	secret_key: Option<[u8; 32]>,
+	public_key: [u8; 32],
+	aux_rand: Option<[u8; 32]>,
+	message: Vec<u8>,
+	signature: [u8; 64],
+	result: bool
+}
+
+fn vectors() -> Vec<Vector> {
+	VECTORS.lines().skip(1).map(|line| {
+		let fields: Vec<&str> = line.split(',').collect();
+		let optional = |hex: &str| if hex.is_empty() { None } else { Some(bytes(hex)) };
+
+		Vector {
+			secret_key: optional(fields[1]),
+			public_key: bytes(fields[2]),
+			aux_rand: optional(fields[3]),
+			message: unhex(fields[4]),
+			signature: bytes(fields[5]),
+			result: fields[6] == "TRUE"
+		}
+	}).collect()
+}
+
+fn verify(vector: &Vector) -> bool {
+	let key = XOnlyPublicKey::from_bytes(&vector.public_key);
+	let sig = SchnorrSignature::from_bytes(&vector.signature);
+
+	match (key, sig) {
+		(Ok(key), Ok(sig)) => schnorr_verify(&key, &vector.message, &sig),
+		_ => false
+	}
+}
+
+#[test]
+fn test_tagged_hash() {
+	let tag = sha256(b"BIP0340/challenge");
+	let mut data = tag.to_vec();
+	data.extend_from_slice(&tag);
+	data.extend_from_slice(b"message");
+
+	assert_eq!(tagged_hash("BIP0340/challenge", b"message"), sha256(&data));
+}
+
+#[test]
+fn test_vectors() {
+	let vectors = vectors();
+	assert_eq!(vectors.len(), 19);
+
+	for (i, vector) in vectors.iter().enumerate() {
+		if let (Some(d), Some(aux_rand)) = (vector.secret_key, vector.aux_rand) {
+			let key = PrivateKey::<Secp256k1>::new(Scalar::from_bytes(&d).unwrap()).unwrap();
+			assert_eq!(XOnlyPublicKey::from_private_key(&key).to_bytes(), vector.public_key, "vector {}", i);
+
+			let sig = schnorr_sign(&key, &vector.message, &aux_rand);
+			assert_eq!(&sig.to_bytes()[..], &vector.signature[..], "vector {}", i);
+		}
+
+		assert_eq!(verify(vector), vector.result, "vector {}", i);
+	}
+}
+
+#[test]
+fn test_decoding_errors() {
+	// Vectors 5, 12, 13 and 14
+	let vectors = vectors();
+	assert_eq!(XOnlyPublicKey::from_bytes(&vectors[5].public_key), Err(PointError::NotOnCurve));
+	assert_eq!(SchnorrSignature::from_bytes(&vectors[12].signature), Err(SignatureError::ComponentOutOfRange));
+	assert_eq!(SchnorrSignature::from_bytes(&vectors[13].signature), Err(SignatureError::ComponentOutOfRange));
+	assert_eq!(XOnlyPublicKey::from_bytes(&vectors[14].public_key), Err(PointError::CoordinateOutOfRange));
+}
+
+#[test]
+fn test_verify_batch() {
+	let vectors = vectors();
+	let parse = |vector: &Vector| {
+		(
+			XOnlyPublicKey::from_bytes(&vector.public_key).unwrap(),
+			SchnorrSignature::from_bytes(&vector.signature).unwrap()
+		)
+	};
+
+	let mut batch: Vec<_> = vectors.iter()
+		.filter(|vector| vector.result)
+		.map(|vector| {
+			let (key, sig) = parse(vector);
+			(key, &vector.message[..], sig)
+		})
+		.collect();
+
+	assert!(schnorr_verify_batch(&[]));
+	assert!(schnorr_verify_batch(&batch[..1]));
+	assert!(schnorr_verify_batch(&batch));
+
+	// Large batches are verified by the Pippenger algorithm
+	let msgs: Vec<[u8; 32]> = (0..40u8).map(|i| sha256(&[i])).collect();
+	let mut large: Vec<_> = msgs.iter()
+		.map(|msg| {
+			let key = PrivateKey::<Secp256k1>::new(Scalar::reduce(&Uint256::from_be_bytes(&sha256(msg)))).unwrap();
+			(XOnlyPublicKey::from_private_key(&key), &msg[..], schnorr_sign(&key, msg, &[0; 32]))
+		})
+		.collect();
+	assert!(schnorr_verify_batch(&large));
+
+	// Invalid signatures with the valid encodings: negated message, negated s, infinite sG - eP
+	let valid = large[17].2;
+	for &i in [6, 7, 8, 9, 10].iter() {
+		let (key, sig) = parse(&vectors[i]);
+		batch.push((key, &vectors[i].message[..], sig));
+		assert!(!schnorr_verify_batch(&batch), "vector {}", i);
+		batch.pop();
+
+		large[17].2 = sig;
+		assert!(!schnorr_verify_batch(&large), "vector {}", i);
+	}
+
+	large[17].2 = valid;
+	assert!(schnorr_verify_batch(&large));
+}
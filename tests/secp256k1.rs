@@ -1,21 +1,45 @@
 extern crate metamint;
 
-use metamint::crypto::ecdsa::{SECP256K1, ECPoint};
-use metamint::utils::bignum::{Uint256, Zero};
+mod common;
+
+use metamint::crypto::ecdsa::{SECP256K1, ECPoint, PointError, PointFormat, FieldElement, Scalar, SECP256K1_P, SECP256K1_N};
+use metamint::crypto::field::PrimeField;
+use metamint::utils::bignum::{Uint256, Zero, One};
+use std::{mem, thread};
+use common::{unhex, hex};
+
+fn fe(raw: [u64; 4]) -> FieldElement {
+	FieldElement::new(Uint256::from_raw(raw))
+}
 
 #[test]
 fn test_generation_point() {
 	//0x 79BE667EF9DCBBAC 55A06295CE870B07 029BFCDB2DCE28D9 59F2815B16F81798
 	//0x 483ADA7726A3C465 5DA4FBFC0E1108A8 FD17B448A6855419 9C47D08FFB10D4B8
-	assert_eq!(*SECP256K1.gen_point(), SECP256K1.create_point(
-		Uint256::from_raw([0x59F2815B16F81798, 0x029BFCDB2DCE28D9, 0x55A06295CE870B07, 0x79BE667EF9DCBBAC]),
-		Uint256::from_raw([0x9C47D08FFB10D4B8, 0xFD17B448A6855419, 0x5DA4FBFC0E1108A8, 0x483ADA7726A3C465])
+	assert_eq!(SECP256K1.gen_point(), SECP256K1.create_point(
+		fe([0x59F2815B16F81798, 0x029BFCDB2DCE28D9, 0x55A06295CE870B07, 0x79BE667EF9DCBBAC]),
+		fe([0x9C47D08FFB10D4B8, 0xFD17B448A6855419, 0x5DA4FBFC0E1108A8, 0x483ADA7726A3C465])
 	));
 }
 
+#[test]
+fn test_point_ownership() {
+	fn assert_owned<T: Send + Sync + 'static>(_: &T) { }
+
+	let g = SECP256K1.gen_point();
+	assert_owned(&g);
+
+	// The curve is defined by the type, the point stores only the coordinates
+	assert!(mem::size_of::<ECPoint>() <= 2 * mem::size_of::<FieldElement>() + mem::size_of::<u64>());
+
+	let p = thread::spawn(move || g.double()).join().unwrap();
+	assert_eq!(p, g + g);
+	assert!(::std::ptr::eq(p.curve(), &SECP256K1));
+}
+
 #[test]
 fn test_double() {
-	let gp = *SECP256K1.gen_point();
+	let gp = SECP256K1.gen_point();
 
 	// 2G:
 	// X: 0x c6047f9441ed7d6d 3045406e95c07cd8 5c778e4b8cef3ca7 abac09b95c709ee5
@@ -23,9 +47,8 @@ fn test_double() {
 
 	assert_eq!(
 		gp.double(), ECPoint::new(
-			Uint256::from_raw([0xabac09b95c709ee5, 0x5c778e4b8cef3ca7, 0x3045406e95c07cd8, 0xc6047f9441ed7d6d]),
-			Uint256::from_raw([0x236431a950cfe52a, 0xf7f632653266d0e1, 0xa3c58419466ceaee, 0x1ae168fea63dc339]),
-			&SECP256K1
+			fe([0xabac09b95c709ee5, 0x5c778e4b8cef3ca7, 0x3045406e95c07cd8, 0xc6047f9441ed7d6d]),
+			fe([0x236431a950cfe52a, 0xf7f632653266d0e1, 0xa3c58419466ceaee, 0x1ae168fea63dc339])
 		)
 	);
 }
@@ -40,32 +63,297 @@ fn test_add() {
 	// X: 0x 2585e5ca09115735 c90559d35cf3cbbf 685cb9ecbfbe242b fb7238c5d735f38a
 	// Y: 0x b1abc72f727dd755 500a2c543d500d80 6acb43da021eee4a 800cd35bf68c3e04
 
-	let A = SECP256K1.create_point(
-		Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]),
-		Uint256::from_raw([0x001209079bbcb8db, 0x6fed42c47bffb081, 0x3692bdff9fcab867, 0xf089012beb484248])
+	let a = SECP256K1.create_point(
+		fe([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]),
+		fe([0x001209079bbcb8db, 0x6fed42c47bffb081, 0x3692bdff9fcab867, 0xf089012beb484248])
 	);
 
 	assert_eq!(
-		A + *SECP256K1.gen_point(),
+		a + SECP256K1.gen_point(),
 		SECP256K1.create_point(
-			Uint256::from_raw([0xfb7238c5d735f38a, 0x685cb9ecbfbe242b, 0xc90559d35cf3cbbf, 0x2585e5ca09115735]),
-			Uint256::from_raw([0x800cd35bf68c3e04, 0x6acb43da021eee4a, 0x500a2c543d500d80, 0xb1abc72f727dd755])
+			fe([0xfb7238c5d735f38a, 0x685cb9ecbfbe242b, 0xc90559d35cf3cbbf, 0x2585e5ca09115735]),
+			fe([0x800cd35bf68c3e04, 0x6acb43da021eee4a, 0x500a2c543d500d80, 0xb1abc72f727dd755])
 		)
 	);
 }
 
 #[test]
 fn test_mul() {
-	let G = *SECP256K1.gen_point();
+	let g = SECP256K1.gen_point();
 
-	let A = SECP256K1.create_point(
-		Uint256::from_raw([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]),
-		Uint256::from_raw([0x001209079bbcb8db, 0x6fed42c47bffb081, 0x3692bdff9fcab867, 0xf089012beb484248])
+	let a = SECP256K1.create_point(
+		fe([0xbbf030a89c42b584, 0x71c944a6003cb334, 0xbdb4b0390695afb4, 0x162ebcd38c90b56f]),
+		fe([0x001209079bbcb8db, 0x6fed42c47bffb081, 0x3692bdff9fcab867, 0xf089012beb484248])
 	);
 
 	// 0x 45b0c38fa5476635 4cf3409d38b87325 5dfa9ed3407a542b a48eb9cab9dfca67
-	let Pk = Uint256::from_raw([0xa48eb9cab9dfca67, 0x5dfa9ed3407a542b, 0x4cf3409d38b87325, 0x45b0c38fa5476635]);
+	let pk = Scalar::new(Uint256::from_raw([0xa48eb9cab9dfca67, 0x5dfa9ed3407a542b, 0x4cf3409d38b87325, 0x45b0c38fa5476635]));
 
 	// Pk * G = A
-	assert_eq!(G * Pk, A);
-}
\ No newline at end of file
+	assert_eq!(g * pk, a);
+}
+
+#[test]
+fn test_mul_vectors() {
+	let g = SECP256K1.gen_point();
+
+	// 3G
+	assert_eq!(g * Scalar::from(3), SECP256K1.create_point(
+		fe([0x8601f113bce036f9, 0xb531c845836f99b0, 0x49344f85f89d5229, 0xf9308a019258c310]),
+		fe([0x6cb9fd7584b8e672, 0x6500a99934c2231b, 0x0fe337e62a37f356, 0x388f7b0f632de814])
+	));
+
+	// 0x ffffffffffffffff ffffffffffffffff * G
+	assert_eq!(g * Scalar::reduce(&Uint256::from_raw([0xffffffffffffffff, 0xffffffffffffffff, 0, 0])), SECP256K1.create_point(
+		fe([0x08522a241e936da8, 0xe6c24a05d9d6b32f, 0xe12569b630710400, 0x6c034fd8cc8bd548]),
+		fe([0x9b8eb3fd33d25f15, 0x9798b51289138f97, 0x3bfa408f7898ea61, 0x47ec36379eabcb79])
+	));
+
+	// 0x aa5e28d6a97a2479 a65527f7290311a3 624d4cc0fa157859 8ee3c2613bf99522 * G
+	assert_eq!(g * Scalar::reduce(&Uint256::from_raw([0x8ee3c2613bf99522, 0x624d4cc0fa157859, 0xa65527f7290311a3, 0xaa5e28d6a97a2479])), SECP256K1.create_point(
+		fe([0xcfacdfa239ff49c6, 0x099aa0ad9fd54ebc, 0x3d192b3c5133a6ba, 0x34f9460f0e4f0839]),
+		fe([0x01bb419e0f2ca232, 0x7728a960686cb5a9, 0x23f6d25a7a91e7dd, 0x0b71ea9bd730fd89])
+	));
+}
+
+#[test]
+fn test_mul_vartime() {
+	let g = SECP256K1.gen_point();
+	let o = SECP256K1.identity();
+	let scalars = [
+		Scalar::zero(),
+		Scalar::one(),
+		Scalar::from(3),
+		-Scalar::one(),
+		Scalar::reduce(&Uint256::from_raw([0x8ee3c2613bf99522, 0x624d4cc0fa157859, 0xa65527f7290311a3, 0xaa5e28d6a97a2479]))
+	];
+
+	for k in scalars.iter() {
+		assert_eq!(g.mul_vartime(k), g * *k);
+		assert_eq!(g.double().mul_vartime(k), g.double() * *k);
+		assert_eq!(o.mul_vartime(k), o);
+	}
+
+	// (n - 1)G = -G
+	assert_eq!(g * -Scalar::one(), -g);
+}
+
+#[test]
+fn test_mul_endomorphism() {
+	let g = SECP256K1.gen_point();
+	let beta = fe([0xc1396c28719501ee, 0x9cf0497512f58995, 0x6e64479eac3434e9, 0x7ae96a2b657c0710]);
+	let lambda = Scalar::new(Uint256::from_raw([0xdf02967c1b23bd72, 0x122e22ea20816678, 0xa5261c028812645a, 0x5363ad4cc05c30e0]));
+
+	// lambda * (x, y) = (beta * x, y)
+	assert_eq!(g.mul_vartime(&lambda), SECP256K1.create_point(beta * *g.x(), *g.y()));
+	assert_eq!(g * lambda, SECP256K1.create_point(beta * *g.x(), *g.y()));
+
+	// Scalars which halves are close to the bounds
+	let scalars = [
+		lambda - Scalar::one(),
+		-lambda,
+		Scalar::reduce(&Uint256::from_raw([0, 0, 1, 0])),
+		Scalar::reduce(&Uint256::from_raw([0xffffffffffffffff, 0xffffffffffffffff, 0, 0])),
+		Scalar::reduce(&(SECP256K1_N / 2)),
+		Scalar::reduce(&(SECP256K1_N / 2 + Uint256::one())),
+		Scalar::reduce(&Uint256::from_raw([0x1234567890abcdef, 0xfedcba0987654321, 0x0f1e2d3c4b5a6978, 0x8796a5b4c3d2e1f0]))
+	];
+
+	for k in scalars.iter() {
+		assert_eq!(g.mul_vartime(k), g * *k);
+		assert_eq!(SECP256K1.double_mul(k, &g, &lambda, &g), g * (*k + lambda));
+	}
+}
+
+#[test]
+fn test_mul_generator() {
+	let g = SECP256K1.gen_point();
+	let scalars = [
+		Scalar::zero(),
+		Scalar::one(),
+		Scalar::from(15),
+		Scalar::from(16),
+		-Scalar::one(),
+		Scalar::reduce(&Uint256::from_raw([0xffffffffffffffff, 0xffffffffffffffff, 0, 0])),
+		Scalar::reduce(&Uint256::from_raw([0x8ee3c2613bf99522, 0x624d4cc0fa157859, 0xa65527f7290311a3, 0xaa5e28d6a97a2479]))
+	];
+
+	for k in scalars.iter() {
+		assert_eq!(SECP256K1.mul_generator(k), g * *k);
+	}
+}
+
+#[test]
+fn test_double_mul() {
+	let g = SECP256K1.gen_point();
+	let q = g * Scalar::from(0x1234_5678);
+	let u1 = Scalar::reduce(&Uint256::from_raw([0x8ee3c2613bf99522, 0x624d4cc0fa157859, 0xa65527f7290311a3, 0xaa5e28d6a97a2479]));
+	let u2 = -Scalar::from(3);
+
+	assert_eq!(SECP256K1.double_mul(&u1, &g, &u2, &q), g * u1 + q * u2);
+	assert_eq!(SECP256K1.double_mul(&u1, &g, &Scalar::zero(), &q), g * u1);
+	assert_eq!(SECP256K1.double_mul(&u1, &g, &u2, &SECP256K1.identity()), g * u1);
+
+	// aG + (-a)G = O
+	assert!(SECP256K1.double_mul(&u1, &g, &-u1, &g).is_identity());
+}
+
+#[test]
+fn test_multi_mul() {
+	let g = SECP256K1.gen_point();
+	assert!(SECP256K1.multi_mul(&[]).is_identity());
+
+	// Both the Strauss and the Pippenger algorithms
+	for &count in [3u64, 80].iter() {
+		let mut terms = Vec::new();
+		let mut expected = SECP256K1.identity();
+
+		for i in 1..=count {
+			let p = SECP256K1.mul_generator(&Scalar::from(i * 31 + 7));
+			let k = Scalar::reduce(&Uint256::from_raw([i.wrapping_mul(0x9e3779b97f4a7c15), !i, i, i << 40]));
+
+			expected = expected + g * (Scalar::from(i * 31 + 7) * k);
+			terms.push((k, p));
+		}
+		terms.push((Scalar::from(5), SECP256K1.identity()));
+
+		assert_eq!(SECP256K1.multi_mul(&terms), expected);
+	}
+}
+
+#[test]
+fn test_identity() {
+	let g = SECP256K1.gen_point();
+	let o = SECP256K1.identity();
+
+	assert!(o.is_identity());
+	assert!(!g.is_identity());
+	assert_eq!(o, ECPoint::identity());
+	assert_ne!(o, g);
+
+	assert_eq!(g + o, g);
+	assert_eq!(o + g, g);
+	assert_eq!(o + o, o);
+	assert_eq!(o.double(), o);
+	assert_eq!(-o, o);
+	assert_eq!(o * Scalar::from(5), o);
+	assert_eq!(g * Scalar::zero(), o);
+}
+
+#[test]
+fn test_neg() {
+	let g = SECP256K1.gen_point();
+
+	// -G:
+	// X: 0x 79be667ef9dcbbac 55a06295ce870b07 029bfcdb2dce28d9 59f2815b16f81798
+	// Y: 0x b7c52588d95c3b9a a25b0403f1eef757 02e84bb7597aabe6 63b82f6f04ef2777
+	assert_eq!(-g, SECP256K1.create_point(
+		fe([0x59f2815b16f81798, 0x029bfcdb2dce28d9, 0x55a06295ce870b07, 0x79be667ef9dcbbac]),
+		fe([0x63b82f6f04ef2777, 0x02e84bb7597aabe6, 0xa25b0403f1eef757, 0xb7c52588d95c3b9a])
+	));
+
+	assert_eq!(-(-g), g);
+	assert_eq!(g * Scalar::reduce(&(SECP256K1_N - Uint256::one())), -g);
+}
+
+#[test]
+fn test_sub() {
+	let g = SECP256K1.gen_point();
+
+	assert!((g - g).is_identity());
+	assert!((g + -g).is_identity());
+	assert_eq!(g + g, g.double());
+	assert_eq!(g.double() - g, g);
+	assert_eq!(g * Scalar::from(5) - g * Scalar::from(3), g.double());
+}
+
+#[test]
+fn test_try_create_point() {
+	let gx = Uint256::from_raw([0x59f2815b16f81798, 0x029bfcdb2dce28d9, 0x55a06295ce870b07, 0x79be667ef9dcbbac]);
+	let gy = Uint256::from_raw([0x9c47d08ffb10d4b8, 0xfd17b448a6855419, 0x5da4fbfc0e1108a8, 0x483ada7726a3c465]);
+
+	assert_eq!(SECP256K1.try_create_point(gx, gy), Ok(SECP256K1.gen_point()));
+
+	// Coordinates should be less than `p`
+	assert_eq!(SECP256K1.try_create_point(SECP256K1_P + Uint256::from(5), gy), Err(PointError::CoordinateOutOfRange));
+	assert_eq!(SECP256K1.try_create_point(gx, SECP256K1_P), Err(PointError::CoordinateOutOfRange));
+
+	// y^2 != x^3 + 7
+	assert_eq!(SECP256K1.try_create_point(gx, gy + Uint256::one()), Err(PointError::NotOnCurve));
+	assert_eq!(SECP256K1.try_create_point(Uint256::zero(), Uint256::zero()), Err(PointError::NotOnCurve));
+}
+
+#[test]
+fn test_is_on_curve() {
+	let g = SECP256K1.gen_point();
+
+	assert!(g.is_on_curve());
+	assert!(g.double().is_on_curve());
+	assert!(SECP256K1.identity().is_on_curve());
+	assert!(!SECP256K1.create_point(*g.x(), *g.x()).is_on_curve());
+
+	assert!(g.is_in_subgroup());
+	assert!(SECP256K1.identity().is_in_subgroup());
+	assert_eq!(SECP256K1.cofactor(), 1);
+}
+
+const G_X: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const G_Y: &str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+const G_NEG_Y: &str = "b7c52588d95c3b9aa25b0403f1eef75702e84bb7597aabe663b82f6f04ef2777";
+
+#[test]
+fn test_encode() {
+	let g = SECP256K1.gen_point();
+
+	assert_eq!(hex(&g.encode(PointFormat::Uncompressed)), format!("04{}{}", G_X, G_Y));
+	assert_eq!(hex(&g.encode(PointFormat::Compressed)), format!("02{}", G_X));
+	assert_eq!(hex(&g.encode(PointFormat::Hybrid)), format!("06{}{}", G_X, G_Y));
+
+	// -G has the odd `y`
+	assert_eq!(hex(&(-g).encode(PointFormat::Compressed)), format!("03{}", G_X));
+	assert_eq!(hex(&(-g).encode(PointFormat::Hybrid)), format!("07{}{}", G_X, G_NEG_Y));
+
+	assert_eq!(SECP256K1.identity().encode(PointFormat::Compressed), vec![0]);
+}
+
+#[test]
+fn test_decode_point() {
+	let g = SECP256K1.gen_point();
+
+	for p in [g, -g, g * Scalar::from(3), SECP256K1.identity()].iter() {
+		for &format in [PointFormat::Uncompressed, PointFormat::Compressed].iter() {
+			assert_eq!(SECP256K1.decode_point(&p.encode(format), false), Ok(*p));
+		}
+
+		assert_eq!(SECP256K1.decode_point(&p.encode(PointFormat::Hybrid), true), Ok(*p));
+	}
+
+	assert_eq!(SECP256K1.decode_point(&unhex(&format!("03{}", G_X)), false), Ok(-g));
+	assert_eq!(SECP256K1.decode_point(&unhex(&format!("04{}{}", G_X, G_NEG_Y)), false), Ok(-g));
+}
+
+#[test]
+fn test_decode_point_errors() {
+	let decode = |hex: String| SECP256K1.decode_point(&unhex(&hex), false);
+
+	// Wrong length or prefix
+	assert_eq!(SECP256K1.decode_point(&[], false), Err(PointError::InvalidEncoding));
+	assert_eq!(decode(format!("02{}00", G_X)), Err(PointError::InvalidEncoding));
+	assert_eq!(decode(format!("04{}", G_X)), Err(PointError::InvalidEncoding));
+	assert_eq!(decode(format!("05{}{}", G_X, G_Y)), Err(PointError::InvalidEncoding));
+	assert_eq!(decode("0000".to_string()), Err(PointError::InvalidEncoding));
+
+	// Hybrid format is rejected by default and should match the parity of `y`
+	assert_eq!(decode(format!("06{}{}", G_X, G_Y)), Err(PointError::HybridEncoding));
+	assert_eq!(SECP256K1.decode_point(&unhex(&format!("07{}{}", G_X, G_Y)), true), Err(PointError::InvalidEncoding));
+
+	// x^3 + 7 isn't a square for x = 5
+	assert_eq!(decode(format!("02{:064x}", 5)), Err(PointError::NotOnCurve));
+	assert_eq!(decode(format!("04{}{}", G_X, G_NEG_Y.replace("b7", "b8"))), Err(PointError::NotOnCurve));
+
+	// x = p
+	assert_eq!(
+		decode("02fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f".to_string()),
+		Err(PointError::CoordinateOutOfRange)
+	);
+}
@@ -18,6 +18,8 @@ fn test_one() {
 
 	assert!(Uint256::from_raw([1, 0, 0, 0]).is_one());
 	assert!(!Uint256::from_raw([1, 2, 3, 4]).is_one());
+	assert!(!Uint256::from(5).is_one());
+	assert!(!Uint256::zero().is_one());
 }
 
 #[test]
@@ -43,6 +45,32 @@ fn test_is_even() {
 	assert!(Uint256::from(1024).is_even());
 }
 
+#[test]
+fn test_bits() {
+	let a = Uint256::from_raw([0xff98fdf13ceff45f, 0xf3bcc7f3f272feed, 0xff235feebdaedaaa, 0x7ffff3f6f1f600df]);
+
+	assert!(a.bit(0));
+	assert!(!a.bit(5));
+	assert!(!a.bit(255));
+	assert!(a.bit(254));
+	assert!(!a.bit(256));
+	assert!(!a.bit(1000));
+
+	assert_eq!(a.bits(), 255);
+	assert_eq!(Uint256::from(1).bits(), 1);
+	assert_eq!(Uint256::zero().bits(), 0);
+}
+
+#[test]
+fn test_be_bytes() {
+	let a = Uint256::from_raw([0xff98fdf13ceff45f, 0xf3bcc7f3f272feed, 0xff235feebdaedaaa, 0x7ffff3f6f1f600df]);
+	let bytes = a.to_be_bytes();
+
+	assert_eq!(&bytes[..8], &[0x7f, 0xff, 0xf3, 0xf6, 0xf1, 0xf6, 0x00, 0xdf]);
+	assert_eq!(&bytes[24..], &[0xff, 0x98, 0xfd, 0xf1, 0x3c, 0xef, 0xf4, 0x5f]);
+	assert_eq!(Uint256::from_be_bytes(&bytes), a);
+}
+
 #[test]
 fn test_partial_eq() {
 	// max = max
@@ -210,12 +238,12 @@ fn test_mul() {
 	assert_eq!(Uint256::from(1) * Uint256::max(), Uint256::max());
 
 	// max(u64) * max(u64) = 0x fffffffffffffffe 0000000000000001
-	assert_eq!(Uint256::from(u64::max_value()) * Uint256::from(u64::max_value()),
+	assert_eq!(Uint256::from(u64::MAX) * Uint256::from(u64::MAX),
 			   Uint256([1, 0xfffffffffffffffe, 0, 0]));
 
 	// a * b = b * a
-	assert_eq!(Uint256::from(u64::max_value() / 2) * Uint256::from(u64::max_value()),
-			   Uint256::from(u64::max_value()) * Uint256::from(u64::max_value() / 2));
+	assert_eq!(Uint256::from(u64::MAX / 2) * Uint256::from(u64::MAX),
+			   Uint256::from(u64::MAX) * Uint256::from(u64::MAX / 2));
 
 	// 0x 010695afb4 71c944a6003cde34 bbf030a89c42b158
 	// *
@@ -236,7 +264,7 @@ fn test_short_div() {
 	assert_eq!(Uint256::from_raw([1, 0xfffffffffffffffe, 0, 0]) / 1, Uint256::from_raw([1, 0xfffffffffffffffe, 0, 0]));
 
 	// a * 2 / 2 = a
-	assert_eq!(Uint256::from(u64::max_value()) * Uint256::from(2) / 2, Uint256::from(u64::max_value()));
+	assert_eq!(Uint256::from(u64::MAX) * Uint256::from(2) / 2, Uint256::from(u64::MAX));
 
 	// if a < b then a / b = 0
 	// a: 9223372036854775807
@@ -41,6 +41,7 @@ fn test_partial_eq() {
 }
 
 #[test]
+#[allow(clippy::nonminimal_bool)]
 fn test_partial_ord() {
 	let a = Uint512::from_raw([1, 2, 3, 4, 5, 6, 7, 8]);
 	let b = Uint512::from_raw([4, 5, 6, 1, 2, 6, 8, 3]);
@@ -158,7 +159,7 @@ fn test_sub() {
 	assert_eq!(Uint512::zero() - Uint512::from(2), Uint512::max() - Uint512::from(1));
 
 	// Partial overflow check
-	assert_eq!(Uint512([0, 1, 1, 0, 0, 0, 0, 0]) - Uint512([0, 2, 0, 0, 0, 0, 0, 0]), Uint512([0, u64::max_value(), 0, 0, 0, 0, 0, 0]));
+	assert_eq!(Uint512([0, 1, 1, 0, 0, 0, 0, 0]) - Uint512([0, 2, 0, 0, 0, 0, 0, 0]), Uint512([0, u64::MAX, 0, 0, 0, 0, 0, 0]));
 
 	// 0x 8e67b7f577ece0d4 75f39940bef10172 d1b1d3e3dfe37902 a396a0c8bc8d1a23 62bc66361b6102d9 67945a3ea4f92682 682b42fc1e2bf2a4 873b033421617f9f
 	// -
@@ -188,14 +189,14 @@ fn test_mul() {
 
 	// max(u64) * max(u64) = 0x FFFFFFFFFFFFFFFE 0000000000000001
 	assert_eq!(
-		Uint512::from(u64::max_value()) * Uint512::from(u64::max_value()),
+		Uint512::from(u64::MAX) * Uint512::from(u64::MAX),
 		Uint512([0x0000000000000001, 0xFFFFFFFFFFFFFFFE, 0, 0, 0, 0, 0, 0])
 	);
 
 	// a * b = b * a
 	assert_eq!(
-		Uint512::from(u64::max_value() / 2) * Uint512::from(u64::max_value()),
-		Uint512::from(u64::max_value()) * Uint512::from(u64::max_value() / 2)
+		Uint512::from(u64::MAX / 2) * Uint512::from(u64::MAX),
+		Uint512::from(u64::MAX) * Uint512::from(u64::MAX / 2)
 	);
 
 	// 0x ec6f129fcc139b63 fddc1c21da170af4 fea0ecbb8c4782d6 54e0de400564b37c
@@ -222,7 +223,7 @@ fn test_mul() {
 
 	assert_eq!(
 		Uint512::from_raw([8886340722334833766, 14229879991289077733, 15469762598397178558, 2661540, 1, 1, 0, 0])
-		* Uint512::from(u64::max_value()),
+		* Uint512::from(u64::MAX),
 		Uint512::from_raw([0x84ad6031e424e39a, 0xb5d7ef589ea1bc80, 0xeecb0d568822ed26, 0xd6afa31ef4edd619, 0x0000000000289ca3, 0, 1, 0])
 	);
 }